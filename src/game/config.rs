@@ -1,8 +1,11 @@
+use crate::graphics::VirtualResolution;
+
 #[derive(Clone, Debug)]
 pub struct Config {
     pub window_title: String,
     pub window_size: (u32, u32),
     pub vsync: bool,
+    pub virtual_resolution: Option<VirtualResolution>,
     pub frames_per_second: f64,
     pub fixed_updates_per_second: f64,
     pub max_fixed_updates_per_frame: f64,
@@ -14,6 +17,7 @@ impl Default for Config {
             window_title: "Anchor Game".to_string(),
             window_size: (640, 480),
             vsync: true,
+            virtual_resolution: None,
             frames_per_second: 60.0,
             fixed_updates_per_second: 60.0,
             max_fixed_updates_per_frame: 3.0,
@@ -29,6 +29,38 @@ impl Bounds {
     pub fn bottom_right(&self) -> Vec2 {
         Vec2::new(self.x + self.w, self.y + self.h)
     }
+
+    pub fn from_min_max<P>(min: P, max: P) -> Self
+    where
+        P: Into<Vec2>,
+    {
+        let min = min.into();
+        let max = max.into();
+        Self::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+
+    pub fn center(&self) -> Vec2 {
+        Vec2::new(self.x + self.w * 0.5, self.y + self.h * 0.5)
+    }
+
+    pub fn contains<P>(&self, point: P) -> bool
+    where
+        P: Into<Vec2>,
+    {
+        let point = point.into();
+
+        point.x >= self.x
+            && point.y >= self.y
+            && point.x <= self.x + self.w
+            && point.y <= self.y + self.h
+    }
+
+    pub fn intersects(&self, other: &Bounds) -> bool {
+        self.x < other.x + other.w
+            && other.x < self.x + self.w
+            && self.y < other.y + other.h
+            && other.y < self.y + self.h
+    }
 }
 
 impl From<[f32; 4]> for Bounds {
@@ -1,10 +1,15 @@
-use crate::graphics::Bounds;
-use glam::{Mat4, Vec2, Vec4};
+use crate::graphics::{Bounds, CameraShake};
+use glam::{Mat4, Vec2, Vec3, Vec4};
 
 #[derive(Clone, Debug)]
 pub struct Camera {
     pub size: Vec2,
     pub anchor_offset: Vec2,
+    pub position: Vec2,
+    pub zoom: f32,
+    pub rotation: f32,
+    pub world_bounds: Option<Bounds>,
+    pub shake: CameraShake,
 }
 
 impl Camera {
@@ -15,6 +20,11 @@ impl Camera {
         Self {
             size: size.into(),
             anchor_offset: Vec2::ZERO,
+            position: Vec2::ZERO,
+            zoom: 1.0,
+            rotation: 0.0,
+            world_bounds: None,
+            shake: CameraShake::default(),
         }
     }
 
@@ -31,10 +41,126 @@ impl Camera {
         self
     }
 
+    pub fn position<P>(mut self, position: P) -> Self
+    where
+        P: Into<Vec2>,
+    {
+        self.position = position.into();
+        self
+    }
+
+    pub fn zoom(mut self, zoom: f32) -> Self {
+        self.zoom = zoom;
+        self
+    }
+
+    pub fn rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn world_bounds<B>(mut self, world_bounds: B) -> Self
+    where
+        B: Into<Bounds>,
+    {
+        self.world_bounds = Some(world_bounds.into());
+        self
+    }
+
+    pub fn shake(mut self, shake: CameraShake) -> Self {
+        self.shake = shake;
+        self
+    }
+
+    pub fn add_trauma(&mut self, trauma: f32) {
+        self.shake.add_trauma(trauma);
+    }
+
+    pub fn update(&mut self, delta: f32) {
+        self.shake.update(delta);
+    }
+
+    pub fn follow<T, D>(&mut self, target: T, dead_zone: D, smoothing: f32, delta: f32)
+    where
+        T: Into<Vec2>,
+        D: Into<Vec2>,
+    {
+        let offset = target.into() - self.position;
+        let dead_zone = dead_zone.into();
+        let excess = offset.signum() * (offset.abs() - dead_zone).max(Vec2::ZERO);
+        let desired_position = self.position + excess;
+
+        self.position = if smoothing > 0.0 {
+            let alpha = 1.0 - (-smoothing * delta).exp();
+            self.position.lerp(desired_position, alpha)
+        } else {
+            desired_position
+        };
+
+        self.clamp_to_world_bounds();
+    }
+
+    pub fn clamp_to_world_bounds(&mut self) {
+        let Some(world_bounds) = self.world_bounds else {
+            return;
+        };
+
+        let min_offset = self.anchor_offset / self.zoom;
+        let max_offset = (self.size - self.anchor_offset) / self.zoom;
+        let min = world_bounds.top_left() + min_offset;
+        let max = world_bounds.bottom_right() - max_offset;
+
+        self.position.x = clamp_or_center(self.position.x, min.x, max.x);
+        self.position.y = clamp_or_center(self.position.y, min.y, max.y);
+    }
+
     pub fn ortho_matrix(&self) -> Mat4 {
         let tl = -self.anchor_offset;
         let br = self.size - self.anchor_offset;
-        Mat4::orthographic_rh(tl.x, br.x, br.y, tl.y, 0.0, 1.0)
+        let projection = Mat4::orthographic_rh(tl.x, br.x, br.y, tl.y, 0.0, 1.0);
+        projection * self.view_matrix()
+    }
+
+    pub fn view_matrix(&self) -> Mat4 {
+        let position = self.position + self.shake.offset();
+        let rotation = self.rotation + self.shake.rotation();
+
+        Mat4::from_scale(Vec3::new(self.zoom, self.zoom, 1.0))
+            * Mat4::from_rotation_z(-rotation)
+            * Mat4::from_translation(Vec3::new(-position.x, -position.y, 0.0))
+    }
+
+    pub fn visible_bounds(&self) -> Bounds {
+        let inverse_view = self.view_matrix().inverse();
+        let tl = -self.anchor_offset;
+        let br = self.size - self.anchor_offset;
+
+        let corners = [tl, Vec2::new(br.x, tl.y), Vec2::new(tl.x, br.y), br]
+            .map(|corner| inverse_view.transform_point3(corner.extend(0.0)).truncate());
+
+        let min = corners
+            .iter()
+            .fold(Vec2::MAX, |min, corner| min.min(*corner));
+        let max = corners
+            .iter()
+            .fold(Vec2::MIN, |max, corner| max.max(*corner));
+        Bounds::from_min_max(min, max)
+    }
+
+    pub fn screen_to_world<C, V>(&self, screen_coords: C, viewport: V) -> Vec2
+    where
+        C: Into<Vec2>,
+        V: Into<Bounds>,
+    {
+        world_coords(screen_coords, viewport, self)
+    }
+
+    pub fn world_to_screen<C, V>(&self, world_coords: C, viewport: V) -> Vec2
+    where
+        C: Into<Vec2>,
+        V: Into<Bounds>,
+    {
+        viewport_coords(world_coords, viewport, self)
     }
 }
 
@@ -44,6 +170,14 @@ impl From<&Camera> for Mat4 {
     }
 }
 
+fn clamp_or_center(value: f32, min: f32, max: f32) -> f32 {
+    if min > max {
+        (min + max) * 0.5
+    } else {
+        value.clamp(min, max)
+    }
+}
+
 pub fn world_coords(
     viewport_coords: impl Into<Vec2>,
     viewport: impl Into<Bounds>,
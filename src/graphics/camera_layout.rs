@@ -0,0 +1,170 @@
+use crate::graphics::{Bounds, Camera};
+use glam::Vec2;
+
+#[derive(Clone, Copy, Debug)]
+pub enum ViewportLayout {
+    Single,
+    Columns(u32),
+    Rows(u32),
+    Grid { columns: u32, rows: u32 },
+    PictureInPicture { inset: Bounds },
+}
+
+impl ViewportLayout {
+    pub fn viewport_count(&self) -> usize {
+        match *self {
+            Self::Single => 1,
+            Self::Columns(columns) => columns.max(1) as usize,
+            Self::Rows(rows) => rows.max(1) as usize,
+            Self::Grid { columns, rows } => (columns.max(1) * rows.max(1)) as usize,
+            Self::PictureInPicture { .. } => 2,
+        }
+    }
+
+    pub fn viewports<A>(&self, area: A, spacing: f32) -> Vec<Bounds>
+    where
+        A: Into<Bounds>,
+    {
+        let area = area.into();
+
+        match *self {
+            Self::Single => vec![area],
+            Self::Columns(columns) => grid_viewports(area, columns, 1, spacing),
+            Self::Rows(rows) => grid_viewports(area, 1, rows, spacing),
+            Self::Grid { columns, rows } => grid_viewports(area, columns, rows, spacing),
+            Self::PictureInPicture { inset } => {
+                let position = area.position() + inset.position() * area.size();
+                let size = inset.size() * area.size();
+                vec![area, Bounds::new(position.x, position.y, size.x, size.y)]
+            }
+        }
+    }
+}
+
+fn grid_viewports(area: Bounds, columns: u32, rows: u32, spacing: f32) -> Vec<Bounds> {
+    let cells = Vec2::new(columns.max(1) as f32, rows.max(1) as f32);
+    let size = ((area.size() - (cells - Vec2::ONE) * spacing) / cells).max(Vec2::ZERO);
+
+    (0..rows.max(1))
+        .flat_map(|row| (0..columns.max(1)).map(move |column| (column, row)))
+        .map(|(column, row)| {
+            let position =
+                area.position() + Vec2::new(column as f32, row as f32) * (size + spacing);
+
+            Bounds::new(position.x, position.y, size.x, size.y)
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug)]
+pub struct CameraView {
+    pub camera: Camera,
+    pub viewport: Bounds,
+}
+
+impl CameraView {
+    pub fn new<V>(camera: Camera, viewport: V) -> Self
+    where
+        V: Into<Bounds>,
+    {
+        Self {
+            camera,
+            viewport: viewport.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CameraLayout {
+    pub layout: ViewportLayout,
+    pub spacing: f32,
+    views: Vec<CameraView>,
+}
+
+impl CameraLayout {
+    pub fn new<A>(layout: ViewportLayout, area: A) -> Self
+    where
+        A: Into<Bounds>,
+    {
+        Self::with_spacing(layout, area, 0.0)
+    }
+
+    pub fn with_spacing<A>(layout: ViewportLayout, area: A, spacing: f32) -> Self
+    where
+        A: Into<Bounds>,
+    {
+        let views = layout
+            .viewports(area, spacing)
+            .into_iter()
+            .map(|viewport| CameraView::new(Camera::from_size(viewport.size()), viewport))
+            .collect();
+
+        Self {
+            layout,
+            spacing,
+            views,
+        }
+    }
+
+    pub fn resize<A>(&mut self, area: A)
+    where
+        A: Into<Bounds>,
+    {
+        let viewports = self.layout.viewports(area, self.spacing);
+        self.views.truncate(viewports.len());
+
+        for (i, viewport) in viewports.into_iter().enumerate() {
+            match self.views.get_mut(i) {
+                Some(view) => {
+                    let camera = &mut view.camera;
+
+                    if camera.size.x > 0.0 && camera.size.y > 0.0 {
+                        camera.anchor_offset *= viewport.size() / camera.size;
+                    }
+
+                    camera.size = viewport.size();
+                    view.viewport = viewport;
+                }
+                None => {
+                    let camera = Camera::from_size(viewport.size());
+                    self.views.push(CameraView::new(camera, viewport));
+                }
+            }
+        }
+    }
+
+    pub fn set_layout<A>(&mut self, layout: ViewportLayout, area: A)
+    where
+        A: Into<Bounds>,
+    {
+        self.layout = layout;
+        self.resize(area);
+    }
+
+    pub fn views(&self) -> &[CameraView] {
+        &self.views
+    }
+
+    pub fn views_mut(&mut self) -> &mut [CameraView] {
+        &mut self.views
+    }
+
+    pub fn view(&self, index: usize) -> Option<&CameraView> {
+        self.views.get(index)
+    }
+
+    pub fn view_mut(&mut self, index: usize) -> Option<&mut CameraView> {
+        self.views.get_mut(index)
+    }
+
+    pub fn view_at<P>(&self, point: P) -> Option<usize>
+    where
+        P: Into<Vec2>,
+    {
+        let point = point.into();
+
+        self.views
+            .iter()
+            .rposition(|view| view.viewport.contains(point))
+    }
+}
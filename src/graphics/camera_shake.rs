@@ -0,0 +1,77 @@
+use glam::Vec2;
+
+#[derive(Clone, Debug)]
+pub struct CameraShake {
+    pub trauma: f32,
+    pub trauma_decay: f32,
+    pub max_offset: Vec2,
+    pub max_rotation: f32,
+    pub frequency: f32,
+    time: f32,
+}
+
+impl CameraShake {
+    pub fn new<O>(max_offset: O, max_rotation: f32) -> Self
+    where
+        O: Into<Vec2>,
+    {
+        Self {
+            max_offset: max_offset.into(),
+            max_rotation,
+            ..Default::default()
+        }
+    }
+
+    pub fn trauma_decay(mut self, trauma_decay: f32) -> Self {
+        self.trauma_decay = trauma_decay;
+        self
+    }
+
+    pub fn frequency(mut self, frequency: f32) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    pub fn add_trauma(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).clamp(0.0, 1.0);
+    }
+
+    pub fn update(&mut self, delta: f32) {
+        self.time += delta;
+        self.trauma = (self.trauma - self.trauma_decay * delta).max(0.0);
+    }
+
+    pub fn offset(&self) -> Vec2 {
+        let intensity = self.intensity();
+        let t = self.time * self.frequency;
+        Vec2::new(noise(t, 0.0), noise(t, 17.0)) * self.max_offset * intensity
+    }
+
+    pub fn rotation(&self) -> f32 {
+        noise(self.time * self.frequency, 43.0) * self.max_rotation * self.intensity()
+    }
+
+    fn intensity(&self) -> f32 {
+        self.trauma * self.trauma
+    }
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            trauma_decay: 1.0,
+            max_offset: Vec2::splat(16.0),
+            max_rotation: 0.1,
+            frequency: 15.0,
+            time: 0.0,
+        }
+    }
+}
+
+fn noise(t: f32, seed: f32) -> f32 {
+    let value =
+        (t + seed).sin() + (t * 2.3 + seed * 1.7).sin() * 0.5 + (t * 4.7 + seed * 2.9).sin() * 0.25;
+
+    value / 1.75
+}
@@ -1,8 +1,16 @@
-use crate::graphics::shape::{Shape, ShapeBatch, ShapeInstance};
-use crate::graphics::sprite::{SpriteBatch, SpriteInstance, Texture};
+use crate::graphics::post_process::PostProcessOutput;
+use crate::graphics::shape::{
+    DrawMode, DynamicGeometry, DynamicShapeBatch, Shape, ShapeBatch, ShapeInstance,
+};
+use crate::graphics::sprite::{
+    SpriteBatch, SpriteGrid, SpriteGridBatch, SpriteInstance, StaticSpriteBatch,
+    StaticSpriteBatchDraw, Texture,
+};
 use crate::graphics::text::Text;
-use crate::graphics::{Bounds, Color, Drawable, GraphicsContext, WgpuContext};
-use glam::Mat4;
+use crate::graphics::{
+    Bounds, Camera, CameraView, Color, Drawable, GraphicsContext, SamplerOptions, WgpuContext,
+};
+use glam::{Mat4, Vec2};
 use std::ops::Range;
 
 #[derive(Clone, Debug)]
@@ -10,7 +18,10 @@ enum CanvasCommand {
     UpdateProjection,
     UpdateViewport(Bounds),
     DrawShapes(ShapeBatch),
+    DrawDynamicShapes(DynamicShapeBatch),
     DrawSprites(SpriteBatch),
+    DrawSpriteGrid(SpriteGridBatch),
+    DrawStaticSprites(StaticSpriteBatchDraw),
     DrawText(Range<u32>),
 }
 
@@ -20,6 +31,7 @@ pub struct Canvas<'a> {
     clear_color: Color,
     commands: Vec<CanvasCommand>,
     projections: Vec<Mat4>,
+    viewport: Bounds,
 }
 
 impl<'a> Canvas<'a> {
@@ -35,6 +47,7 @@ impl<'a> Canvas<'a> {
 
         let surface_texture = graphics.surface_texture.take().unwrap();
         let projection = graphics.default_camera().ortho_matrix();
+        let viewport = graphics.default_viewport();
 
         Self {
             graphics,
@@ -42,6 +55,7 @@ impl<'a> Canvas<'a> {
             clear_color: Color::BLACK,
             commands: vec![CanvasCommand::UpdateProjection],
             projections: vec![projection],
+            viewport,
         }
     }
 
@@ -66,11 +80,20 @@ impl<'a> Canvas<'a> {
         }
     }
 
+    pub fn projection(&self) -> Mat4 {
+        *self.projections.last().unwrap()
+    }
+
+    pub fn viewport(&self) -> Bounds {
+        self.viewport
+    }
+
     pub fn set_viewport<V>(&mut self, viewport: V)
     where
         V: Into<Bounds>,
     {
         let viewport = viewport.into();
+        self.viewport = viewport;
 
         match self.commands.last_mut() {
             Some(CanvasCommand::UpdateViewport(old_viewport)) => {
@@ -82,6 +105,31 @@ impl<'a> Canvas<'a> {
         }
     }
 
+    pub fn with_camera<F>(&mut self, camera: &Camera, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let old_projection = self.projection();
+
+        self.set_projection(camera);
+        f(self);
+        self.set_projection(old_projection);
+    }
+
+    pub fn with_view<F>(&mut self, view: &CameraView, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let old_projection = self.projection();
+        let old_viewport = self.viewport;
+
+        self.set_projection(&view.camera);
+        self.set_viewport(view.viewport);
+        f(self);
+        self.set_projection(old_projection);
+        self.set_viewport(old_viewport);
+    }
+
     pub fn draw<D>(&mut self, drawable: D)
     where
         D: Drawable,
@@ -91,12 +139,18 @@ impl<'a> Canvas<'a> {
 
     pub fn draw_shape(&mut self, shape: &Shape, shape_instance: ShapeInstance) {
         match self.commands.last_mut() {
-            Some(CanvasCommand::DrawShapes(batch)) if &batch.shape == shape => {
+            Some(CanvasCommand::DrawShapes(batch))
+                if &batch.shape == shape && batch.texture.is_none() =>
+            {
                 batch.instances.end += 1;
             }
             _ => {
                 self.commands.push(CanvasCommand::DrawShapes(
-                    self.graphics.shape_renderer.next_batch(shape.clone()),
+                    self.graphics.shape_renderer.next_batch(
+                        shape.clone(),
+                        None,
+                        SamplerOptions::default(),
+                    ),
                 ));
             }
         }
@@ -104,23 +158,118 @@ impl<'a> Canvas<'a> {
         self.graphics.shape_renderer.add(shape_instance);
     }
 
+    pub fn draw_textured_shape(
+        &mut self,
+        shape: &Shape,
+        texture: &Texture,
+        sampler: SamplerOptions,
+        shape_instance: ShapeInstance,
+    ) {
+        match self.commands.last_mut() {
+            Some(CanvasCommand::DrawShapes(batch))
+                if &batch.shape == shape
+                    && batch.texture.as_ref() == Some(texture)
+                    && batch.sampler == sampler =>
+            {
+                batch.instances.end += 1;
+            }
+            _ => {
+                self.graphics
+                    .sampler_cache
+                    .insert(&self.graphics.wgpu, sampler);
+
+                self.commands.push(CanvasCommand::DrawShapes(
+                    self.graphics.shape_renderer.next_batch(
+                        shape.clone(),
+                        Some(texture.clone()),
+                        sampler,
+                    ),
+                ));
+            }
+        }
+
+        self.graphics.shape_renderer.add(shape_instance);
+    }
+
+    pub fn draw_line<A, B>(&mut self, from: A, to: B, thickness: f32, color: Color)
+    where
+        A: Into<Vec2>,
+        B: Into<Vec2>,
+    {
+        let (from, to) = (from.into(), to.into());
+        self.draw_dynamic_shape(|geometry| geometry.line(from, to, thickness, color));
+    }
+
+    pub fn draw_rect<B>(&mut self, bounds: B, mode: DrawMode, color: Color)
+    where
+        B: Into<Bounds>,
+    {
+        let bounds = bounds.into();
+        self.draw_dynamic_shape(|geometry| geometry.rect(bounds, mode, color));
+    }
+
+    pub fn draw_circle<C>(&mut self, center: C, radius: f32, mode: DrawMode, color: Color)
+    where
+        C: Into<Vec2>,
+    {
+        let center = center.into();
+        self.draw_dynamic_shape(|geometry| geometry.circle(center, radius, mode, color));
+    }
+
+    pub fn draw_polygon(&mut self, points: &[Vec2], mode: DrawMode, color: Color) {
+        self.draw_dynamic_shape(|geometry| geometry.polygon(points, mode, color));
+    }
+
+    pub fn draw_polyline(&mut self, points: &[Vec2], thickness: f32, color: Color) {
+        self.draw_dynamic_shape(|geometry| geometry.polyline(points, thickness, color));
+    }
+
+    fn draw_dynamic_shape<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut DynamicGeometry),
+    {
+        let shape_renderer = &mut self.graphics.shape_renderer;
+        let start = shape_renderer.dynamic_geometry().index_count();
+        f(shape_renderer.dynamic_geometry());
+        let end = shape_renderer.dynamic_geometry().index_count();
+
+        if start == end {
+            return;
+        }
+
+        match self.commands.last_mut() {
+            Some(CanvasCommand::DrawDynamicShapes(batch)) => batch.indexes.end = end,
+            _ => {
+                self.commands
+                    .push(CanvasCommand::DrawDynamicShapes(DynamicShapeBatch {
+                        indexes: start..end,
+                        instance: shape_renderer.add_dynamic_instance(),
+                    }));
+            }
+        }
+    }
+
     pub fn draw_sprite(
         &mut self,
         texture: &Texture,
-        smooth: bool,
+        sampler: SamplerOptions,
         sprite_instance: SpriteInstance,
     ) {
         match self.commands.last_mut() {
             Some(CanvasCommand::DrawSprites(batch))
-                if &batch.texture == texture && batch.smooth == smooth =>
+                if &batch.texture == texture && batch.sampler == sampler =>
             {
                 batch.instances.end += 1;
             }
             _ => {
+                self.graphics
+                    .sampler_cache
+                    .insert(&self.graphics.wgpu, sampler);
+
                 self.commands.push(CanvasCommand::DrawSprites(
                     self.graphics
                         .sprite_renderer
-                        .next_batch(texture.clone(), smooth),
+                        .next_batch(texture.clone(), sampler),
                 ));
             }
         }
@@ -128,6 +277,41 @@ impl<'a> Canvas<'a> {
         self.graphics.sprite_renderer.add(sprite_instance);
     }
 
+    pub fn draw_sprite_grid(
+        &mut self,
+        texture: &Texture,
+        sampler: SamplerOptions,
+        grid: &SpriteGrid,
+        sprite_instance: SpriteInstance,
+    ) {
+        self.graphics
+            .sampler_cache
+            .insert(&self.graphics.wgpu, sampler);
+
+        let batch = self.graphics.sprite_renderer.add_grid(
+            texture.clone(),
+            sampler,
+            grid,
+            sprite_instance,
+        );
+
+        self.commands.push(CanvasCommand::DrawSpriteGrid(batch));
+    }
+
+    pub fn draw_static_sprites(&mut self, batch: &mut StaticSpriteBatch) {
+        batch.upload(&self.graphics.wgpu);
+
+        let Some(draw) = batch.to_draw() else {
+            return;
+        };
+
+        self.graphics
+            .sampler_cache
+            .insert(&self.graphics.wgpu, draw.sampler);
+
+        self.commands.push(CanvasCommand::DrawStaticSprites(draw));
+    }
+
     pub fn draw_text(&mut self, text: Text) {
         let text_index = self.graphics.text_renderer.add(text);
 
@@ -141,6 +325,16 @@ impl<'a> Canvas<'a> {
     }
 
     pub fn present(self) {
+        let virtual_resolution = self.graphics.virtual_resolution;
+        let offscreen = virtual_resolution.is_some() || self.graphics.post_processor.is_active();
+
+        if offscreen {
+            let render_size = self.graphics.render_size();
+            self.graphics
+                .post_processor
+                .prepare(&self.graphics.wgpu, render_size);
+        }
+
         self.graphics.shape_renderer.end(&self.graphics.wgpu);
         self.graphics.sprite_renderer.end(&self.graphics.wgpu);
         self.graphics.text_renderer.end(&self.graphics.wgpu);
@@ -172,11 +366,17 @@ impl<'a> Canvas<'a> {
             .texture
             .create_view(&Default::default());
 
+        let target_view = if offscreen {
+            self.graphics.post_processor.scene_target().view()
+        } else {
+            &surface_view
+        };
+
         {
             let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("render_pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &surface_view,
+                    view: target_view,
                     resolve_target: None,
                     ops: wgpu::Operations {
                         load: wgpu::LoadOp::Clear(self.clear_color.into()),
@@ -199,12 +399,49 @@ impl<'a> Canvas<'a> {
                         pass.set_viewport(viewport.x, viewport.y, viewport.w, viewport.h, 0.0, 1.0);
                     }
                     CanvasCommand::DrawShapes(batch) => {
-                        if !matches!(last_draw_command, CanvasCommand::DrawShapes(_)) {
-                            self.graphics.shape_renderer.prepare_pipeline(&mut pass);
+                        let textured = batch.texture.is_some();
+
+                        let prepared = match last_draw_command {
+                            CanvasCommand::DrawShapes(last_batch) => {
+                                last_batch.texture.is_some() == textured
+                            }
+                            CanvasCommand::DrawDynamicShapes(_) => !textured,
+                            _ => false,
+                        };
+
+                        if !prepared {
+                            self.graphics
+                                .shape_renderer
+                                .prepare_pipeline(&mut pass, textured);
+                            last_draw_command = command;
+                        }
+
+                        let texture_bind_groups = batch.texture.as_ref().map(|texture| {
+                            (
+                                texture.bind_group(),
+                                self.graphics.sampler_cache.get(&batch.sampler),
+                            )
+                        });
+
+                        self.graphics
+                            .shape_renderer
+                            .draw(&mut pass, batch, texture_bind_groups);
+                    }
+                    CanvasCommand::DrawDynamicShapes(batch) => {
+                        let prepared = match last_draw_command {
+                            CanvasCommand::DrawShapes(last_batch) => last_batch.texture.is_none(),
+                            CanvasCommand::DrawDynamicShapes(_) => true,
+                            _ => false,
+                        };
+
+                        if !prepared {
+                            self.graphics
+                                .shape_renderer
+                                .prepare_pipeline(&mut pass, false);
                             last_draw_command = command;
                         }
 
-                        self.graphics.shape_renderer.draw(&mut pass, batch);
+                        self.graphics.shape_renderer.draw_dynamic(&mut pass, batch);
                     }
                     CanvasCommand::DrawSprites(batch) => {
                         if !matches!(last_draw_command, CanvasCommand::DrawSprites(_)) {
@@ -212,16 +449,38 @@ impl<'a> Canvas<'a> {
                             last_draw_command = command;
                         }
 
-                        let sampler_bind_group = if batch.smooth {
-                            &self.graphics.linear_sampler_bind_group
-                        } else {
-                            &self.graphics.nearest_sampler_bind_group
-                        };
+                        self.graphics.sprite_renderer.draw(
+                            &mut pass,
+                            batch.texture.bind_group(),
+                            self.graphics.sampler_cache.get(&batch.sampler),
+                            batch.instances.clone(),
+                        );
+                    }
+                    CanvasCommand::DrawSpriteGrid(batch) => {
+                        if !matches!(last_draw_command, CanvasCommand::DrawSpriteGrid(_)) {
+                            self.graphics
+                                .sprite_renderer
+                                .prepare_grid_pipeline(&mut pass);
+                            last_draw_command = command;
+                        }
+
+                        self.graphics.sprite_renderer.draw_grid(
+                            &mut pass,
+                            batch.texture.bind_group(),
+                            self.graphics.sampler_cache.get(&batch.sampler),
+                            batch,
+                        );
+                    }
+                    CanvasCommand::DrawStaticSprites(batch) => {
+                        self.graphics
+                            .sprite_renderer
+                            .prepare_static_pipeline(&mut pass, &batch.instance_buffer);
+                        last_draw_command = command;
 
                         self.graphics.sprite_renderer.draw(
                             &mut pass,
                             batch.texture.bind_group(),
-                            sampler_bind_group,
+                            self.graphics.sampler_cache.get(&batch.sampler),
                             batch.instances.clone(),
                         );
                     }
@@ -233,7 +492,7 @@ impl<'a> Canvas<'a> {
 
                         self.graphics.text_renderer.draw(
                             &mut pass,
-                            &self.graphics.linear_sampler_bind_group,
+                            self.graphics.sampler_cache.get(&SamplerOptions::LINEAR),
                             text_range.clone(),
                         );
                     }
@@ -241,6 +500,26 @@ impl<'a> Canvas<'a> {
             }
         }
 
+        if offscreen {
+            let output_sampler = match virtual_resolution {
+                Some(virtual_resolution) if !virtual_resolution.smooth => SamplerOptions::NEAREST,
+                _ => SamplerOptions::LINEAR,
+            };
+
+            self.graphics.post_processor.run(
+                &mut encoder,
+                self.graphics.sampler_cache.get(&SamplerOptions::LINEAR),
+                PostProcessOutput {
+                    view: &surface_view,
+                    viewport: virtual_resolution.map(|_| self.graphics.output_viewport()),
+                    clear_color: virtual_resolution.map_or(Color::BLACK, |virtual_resolution| {
+                        virtual_resolution.bar_color
+                    }),
+                    sampler_bind_group: self.graphics.sampler_cache.get(&output_sampler),
+                },
+            );
+        }
+
         self.graphics.wgpu.queue().submit(Some(encoder.finish()));
         self.surface_texture.present();
     }
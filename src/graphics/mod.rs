@@ -1,38 +1,53 @@
+pub mod post_process;
 pub mod shape;
 pub mod sprite;
 pub mod text;
 
+#[cfg(feature = "tiled")]
+pub mod tilemap;
+
 mod bounds;
 mod camera;
+mod camera_layout;
 mod camera_manager;
+mod camera_shake;
 mod canvas;
 mod color;
 mod drawable;
 mod relative_anchor;
+mod render_target;
+mod sampler;
 mod shared_bind_group_layouts;
 mod transform;
 mod utils;
+mod virtual_resolution;
 mod wgpu_context;
 
 pub use self::bounds::*;
 pub use self::camera::*;
+pub use self::camera_layout::*;
 pub use self::camera_manager::*;
+pub use self::camera_shake::*;
 pub use self::canvas::*;
 pub use self::color::*;
 pub use self::drawable::*;
 pub use self::relative_anchor::*;
+pub use self::render_target::*;
+pub use self::sampler::*;
 pub use self::shared_bind_group_layouts::*;
 pub use self::transform::*;
+pub use self::virtual_resolution::*;
 pub use self::wgpu_context::*;
 
 pub(crate) use self::utils::*;
 
 use crate::game::{Config, GameResult};
+use crate::graphics::post_process::PostProcessor;
 use crate::graphics::shape::ShapeRenderer;
 use crate::graphics::sprite::SpriteRenderer;
-use crate::graphics::text::TextRenderer;
+use crate::graphics::text::{GlyphPosition, Text, TextRenderer};
 use anyhow::anyhow;
-use glam::{UVec2, Vec2};
+use glam::{Mat4, UVec2, Vec2};
 use std::sync::Arc;
 use winit::dpi::PhysicalSize;
 use winit::event_loop::EventLoopWindowTarget;
@@ -48,16 +63,17 @@ pub struct GraphicsContext {
     surface_config: wgpu::SurfaceConfiguration,
     surface_texture: Option<wgpu::SurfaceTexture>,
     window: Arc<Window>,
+    virtual_resolution: Option<VirtualResolution>,
 
     // Bind groups
-    nearest_sampler_bind_group: wgpu::BindGroup,
-    linear_sampler_bind_group: wgpu::BindGroup,
+    sampler_cache: SamplerCache,
     projection_bind_group_allocator: ProjectionBindGroupAllocator,
 
     // Renderers
     shape_renderer: ShapeRenderer,
     sprite_renderer: SpriteRenderer,
     text_renderer: TextRenderer,
+    post_processor: PostProcessor,
 }
 
 impl GraphicsContext {
@@ -95,6 +111,8 @@ impl GraphicsContext {
             .request_device(
                 &wgpu::DeviceDescriptor {
                     label: Some("graphics_context_device"),
+                    required_features: adapter.features()
+                        & wgpu::Features::ADDRESS_MODE_CLAMP_TO_BORDER,
                     ..Default::default()
                 },
                 None,
@@ -135,37 +153,7 @@ impl GraphicsContext {
         let wgpu = WgpuContext::new(device, queue);
         let bind_group_layouts = SharedBindGroupLayouts::new(wgpu.device());
 
-        let nearest_sampler = wgpu.device().create_sampler(&wgpu::SamplerDescriptor {
-            min_filter: wgpu::FilterMode::Nearest,
-            mag_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        });
-
-        let nearest_sampler_bind_group =
-            wgpu.device().create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("nearest_sampler_bind_group"),
-                layout: bind_group_layouts.sampler(),
-                entries: &[wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::Sampler(&nearest_sampler),
-                }],
-            });
-
-        let linear_sampler = wgpu.device().create_sampler(&wgpu::SamplerDescriptor {
-            min_filter: wgpu::FilterMode::Linear,
-            mag_filter: wgpu::FilterMode::Linear,
-            ..Default::default()
-        });
-
-        let linear_sampler_bind_group =
-            wgpu.device().create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("linear_sampler_bind_group"),
-                layout: bind_group_layouts.sampler(),
-                entries: &[wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::Sampler(&linear_sampler),
-                }],
-            });
+        let sampler_cache = SamplerCache::new(&wgpu, bind_group_layouts.clone());
 
         let projection_bind_group_allocator =
             ProjectionBindGroupAllocator::new(bind_group_layouts.clone());
@@ -179,6 +167,9 @@ impl GraphicsContext {
         let text_renderer =
             TextRenderer::new(&wgpu, bind_group_layouts.clone(), surface_config.format, 1);
 
+        let post_processor =
+            PostProcessor::new(&wgpu, bind_group_layouts.clone(), surface_config.format);
+
         Ok(Self {
             wgpu,
             bind_group_layouts,
@@ -186,12 +177,13 @@ impl GraphicsContext {
             surface_config,
             surface_texture: None,
             window,
-            nearest_sampler_bind_group,
-            linear_sampler_bind_group,
+            virtual_resolution: config.virtual_resolution,
+            sampler_cache,
             projection_bind_group_allocator,
             shape_renderer,
             sprite_renderer,
             text_renderer,
+            post_processor,
         })
     }
 
@@ -211,6 +203,10 @@ impl GraphicsContext {
         UVec2::new(self.surface_config.width, self.surface_config.height)
     }
 
+    pub fn surface_format(&self) -> wgpu::TextureFormat {
+        self.surface_config.format
+    }
+
     pub fn vsync(&self) -> bool {
         match self.surface_config.present_mode {
             wgpu::PresentMode::AutoVsync => true,
@@ -219,20 +215,101 @@ impl GraphicsContext {
         }
     }
 
+    pub fn virtual_resolution(&self) -> Option<&VirtualResolution> {
+        self.virtual_resolution.as_ref()
+    }
+
+    pub fn set_virtual_resolution(&mut self, virtual_resolution: Option<VirtualResolution>) {
+        self.virtual_resolution = virtual_resolution;
+    }
+
+    pub fn render_size(&self) -> UVec2 {
+        match self.virtual_resolution.as_ref() {
+            Some(virtual_resolution) => virtual_resolution.size(),
+            None => self.surface_size(),
+        }
+    }
+
+    pub fn output_viewport(&self) -> Bounds {
+        match self.virtual_resolution.as_ref() {
+            Some(virtual_resolution) => virtual_resolution.output_viewport(self.surface_size()),
+            None => self.default_viewport(),
+        }
+    }
+
     pub fn default_viewport(&self) -> Bounds {
-        Bounds::new(
-            0.0,
-            0.0,
-            self.surface_config.width as _,
-            self.surface_config.height as _,
-        )
+        let render_size = self.render_size().as_vec2();
+        Bounds::new(0.0, 0.0, render_size.x, render_size.y)
     }
 
     pub fn default_camera(&self) -> Camera {
-        Camera::from_size(Vec2::new(
-            self.surface_config.width as _,
-            self.surface_config.height as _,
-        ))
+        Camera::from_size(self.render_size().as_vec2())
+    }
+
+    pub fn logical_coords<C>(&self, window_coords: C) -> Vec2
+    where
+        C: Into<Vec2>,
+    {
+        match self.virtual_resolution.as_ref() {
+            Some(virtual_resolution) => {
+                virtual_resolution.logical_coords(window_coords, self.surface_size())
+            }
+            None => window_coords.into(),
+        }
+    }
+
+    pub fn window_coords<C>(&self, logical_coords: C) -> Vec2
+    where
+        C: Into<Vec2>,
+    {
+        match self.virtual_resolution.as_ref() {
+            Some(virtual_resolution) => {
+                virtual_resolution.window_coords(logical_coords, self.surface_size())
+            }
+            None => logical_coords.into(),
+        }
+    }
+
+    pub fn world_coords<C, V, P>(&self, window_coords: C, viewport: V, projection: P) -> Vec2
+    where
+        C: Into<Vec2>,
+        V: Into<Bounds>,
+        P: Into<Mat4>,
+    {
+        world_coords(self.logical_coords(window_coords), viewport, projection)
+    }
+
+    pub fn viewport_coords<C, V, P>(&self, world_coords: C, viewport: V, projection: P) -> Vec2
+    where
+        C: Into<Vec2>,
+        V: Into<Bounds>,
+        P: Into<Mat4>,
+    {
+        self.window_coords(viewport_coords(world_coords, viewport, projection))
+    }
+
+    pub fn sampler_cache(&self) -> &SamplerCache {
+        &self.sampler_cache
+    }
+
+    pub fn sampler_cache_mut(&mut self) -> &mut SamplerCache {
+        &mut self.sampler_cache
+    }
+
+    pub fn post_processor(&self) -> &PostProcessor {
+        &self.post_processor
+    }
+
+    pub fn post_processor_mut(&mut self) -> &mut PostProcessor {
+        &mut self.post_processor
+    }
+
+    pub fn measure_text(&mut self, text: &Text) -> Vec2 {
+        self.text_renderer.measure_text(text)
+    }
+
+    pub fn glyph_positions(&mut self, text: &Text) -> Vec<GlyphPosition> {
+        self.text_renderer.glyph_positions(text)
     }
 
     pub fn update_surface_texture(&mut self) -> bool {
@@ -0,0 +1,42 @@
+use crate::graphics::post_process::PostProcessPass;
+use crate::graphics::GraphicsContext;
+use bytemuck::{Pod, Zeroable};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Bloom {
+    pub threshold: f32,
+    pub intensity: f32,
+    pub radius: f32,
+    pub _padding: f32,
+}
+
+impl Default for Bloom {
+    fn default() -> Self {
+        Self {
+            threshold: 0.8,
+            intensity: 1.0,
+            radius: 8.0,
+            _padding: 0.0,
+        }
+    }
+}
+
+impl PostProcessPass {
+    pub fn bloom<G>(graphics: G, bloom: Bloom) -> Self
+    where
+        G: AsRef<GraphicsContext>,
+    {
+        let graphics = graphics.as_ref();
+
+        Self::create(
+            &graphics.wgpu,
+            &graphics.bind_group_layouts,
+            graphics.surface_format(),
+            "bloom",
+            include_str!("../shaders/post_process/bloom.wgsl"),
+            bytemuck::bytes_of(&bloom),
+            None,
+        )
+    }
+}
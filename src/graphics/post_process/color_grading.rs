@@ -0,0 +1,133 @@
+use crate::game::GameResult;
+use crate::graphics::post_process::PostProcessPass;
+use crate::graphics::{open_image, GraphicsContext};
+use anyhow::bail;
+use bytemuck::{Pod, Zeroable};
+use std::path::Path;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ColorGrading {
+    pub intensity: f32,
+    pub _padding: [f32; 3],
+}
+
+impl Default for ColorGrading {
+    fn default() -> Self {
+        Self {
+            intensity: 1.0,
+            _padding: [0.0; 3],
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ColorLut {
+    view: Arc<wgpu::TextureView>,
+    size: u32,
+}
+
+impl ColorLut {
+    pub fn from_file<G, P>(graphics: G, path: P) -> GameResult<Self>
+    where
+        G: AsRef<GraphicsContext>,
+        P: AsRef<Path>,
+    {
+        let graphics = graphics.as_ref();
+        let path = path.as_ref();
+
+        let image = open_image(path)?.to_rgba8();
+
+        let size = image.height();
+
+        if size == 0 || image.width() != size * size {
+            bail!(
+                "Invalid LUT size {}x{} in '{}', expected a horizontal strip of {}x{}",
+                image.width(),
+                image.height(),
+                path.display(),
+                size * size,
+                size,
+            );
+        }
+
+        let mut data = Vec::with_capacity((size * size * size * 4) as usize);
+
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    data.extend_from_slice(&image.get_pixel(b * size + r, g).0);
+                }
+            }
+        }
+
+        let view = graphics
+            .device()
+            .create_texture_with_data(
+                graphics.queue(),
+                &wgpu::TextureDescriptor {
+                    label: Some("color_lut_texture"),
+                    size: wgpu::Extent3d {
+                        width: size,
+                        height: size,
+                        depth_or_array_layers: size,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D3,
+                    format: wgpu::TextureFormat::Rgba8Unorm,
+                    usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                },
+                wgpu::util::TextureDataOrder::default(),
+                &data,
+            )
+            .create_view(&Default::default());
+
+        Ok(Self {
+            view: Arc::new(view),
+            size,
+        })
+    }
+
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+}
+
+impl PostProcessPass {
+    pub fn color_grading<G>(graphics: G, lut: &ColorLut, color_grading: ColorGrading) -> Self
+    where
+        G: AsRef<GraphicsContext>,
+    {
+        let graphics = graphics.as_ref();
+        let layout = graphics.bind_group_layouts.texture_3d();
+
+        let lut_bind_group = graphics
+            .device()
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("color_lut_bind_group"),
+                layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(lut.view()),
+                }],
+            });
+
+        Self::create(
+            &graphics.wgpu,
+            &graphics.bind_group_layouts,
+            graphics.surface_format(),
+            "color_grading",
+            include_str!("../shaders/post_process/color_grading.wgsl"),
+            bytemuck::bytes_of(&color_grading),
+            Some((layout, lut_bind_group)),
+        )
+    }
+}
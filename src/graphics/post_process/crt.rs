@@ -0,0 +1,42 @@
+use crate::graphics::post_process::PostProcessPass;
+use crate::graphics::GraphicsContext;
+use bytemuck::{Pod, Zeroable};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Crt {
+    pub curvature: f32,
+    pub scanline_intensity: f32,
+    pub scanline_count: f32,
+    pub aberration: f32,
+}
+
+impl Default for Crt {
+    fn default() -> Self {
+        Self {
+            curvature: 0.1,
+            scanline_intensity: 0.3,
+            scanline_count: 240.0,
+            aberration: 1.0,
+        }
+    }
+}
+
+impl PostProcessPass {
+    pub fn crt<G>(graphics: G, crt: Crt) -> Self
+    where
+        G: AsRef<GraphicsContext>,
+    {
+        let graphics = graphics.as_ref();
+
+        Self::create(
+            &graphics.wgpu,
+            &graphics.bind_group_layouts,
+            graphics.surface_format(),
+            "crt",
+            include_str!("../shaders/post_process/crt.wgsl"),
+            bytemuck::bytes_of(&crt),
+            None,
+        )
+    }
+}
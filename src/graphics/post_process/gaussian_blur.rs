@@ -0,0 +1,55 @@
+use crate::graphics::post_process::PostProcessPass;
+use crate::graphics::GraphicsContext;
+use bytemuck::{Pod, Zeroable};
+use glam::Vec2;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct GaussianBlur {
+    pub direction: Vec2,
+    pub radius: f32,
+    pub sigma: f32,
+}
+
+impl GaussianBlur {
+    pub fn horizontal(radius: f32) -> Self {
+        Self {
+            direction: Vec2::X,
+            radius,
+            sigma: radius * 0.5,
+        }
+    }
+
+    pub fn vertical(radius: f32) -> Self {
+        Self {
+            direction: Vec2::Y,
+            radius,
+            sigma: radius * 0.5,
+        }
+    }
+}
+
+impl Default for GaussianBlur {
+    fn default() -> Self {
+        Self::horizontal(4.0)
+    }
+}
+
+impl PostProcessPass {
+    pub fn gaussian_blur<G>(graphics: G, blur: GaussianBlur) -> Self
+    where
+        G: AsRef<GraphicsContext>,
+    {
+        let graphics = graphics.as_ref();
+
+        Self::create(
+            &graphics.wgpu,
+            &graphics.bind_group_layouts,
+            graphics.surface_format(),
+            "gaussian_blur",
+            include_str!("../shaders/post_process/gaussian_blur.wgsl"),
+            bytemuck::bytes_of(&blur),
+            None,
+        )
+    }
+}
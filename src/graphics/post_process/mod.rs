@@ -0,0 +1,174 @@
+mod bloom;
+mod color_grading;
+mod crt;
+mod gaussian_blur;
+mod post_process_pass;
+mod vignette;
+
+pub use self::bloom::*;
+pub use self::color_grading::*;
+pub use self::crt::*;
+pub use self::gaussian_blur::*;
+pub use self::post_process_pass::*;
+pub use self::vignette::*;
+
+use crate::graphics::{Bounds, Color, RenderTarget, SharedBindGroupLayouts, WgpuContext};
+use glam::UVec2;
+
+#[derive(Clone, Copy, Debug)]
+pub struct PostProcessOutput<'a> {
+    pub view: &'a wgpu::TextureView,
+    pub viewport: Option<Bounds>,
+    pub clear_color: Color,
+    pub sampler_bind_group: &'a wgpu::BindGroup,
+}
+
+#[derive(Debug)]
+pub struct PostProcessor {
+    bind_group_layouts: SharedBindGroupLayouts,
+    format: wgpu::TextureFormat,
+    targets: Vec<RenderTarget>,
+    passes: Vec<PostProcessPass>,
+    blit_pass: PostProcessPass,
+}
+
+impl PostProcessor {
+    pub fn new(
+        wgpu: &WgpuContext,
+        bind_group_layouts: SharedBindGroupLayouts,
+        format: wgpu::TextureFormat,
+    ) -> Self {
+        let blit_pass = PostProcessPass::create(
+            wgpu,
+            &bind_group_layouts,
+            format,
+            "blit",
+            include_str!("../shaders/post_process/blit.wgsl"),
+            &[],
+            None,
+        );
+
+        Self {
+            bind_group_layouts,
+            format,
+            targets: Vec::new(),
+            passes: Vec::new(),
+            blit_pass,
+        }
+    }
+
+    pub fn passes(&self) -> &[PostProcessPass] {
+        &self.passes
+    }
+
+    pub fn passes_mut(&mut self) -> &mut Vec<PostProcessPass> {
+        &mut self.passes
+    }
+
+    pub fn push(&mut self, pass: PostProcessPass) -> usize {
+        self.passes.push(pass);
+        self.passes.len() - 1
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.passes.iter().any(PostProcessPass::enabled)
+    }
+
+    pub fn prepare(&mut self, wgpu: &WgpuContext, size: UVec2) {
+        let size = size.max(UVec2::ONE);
+
+        if self
+            .targets
+            .first()
+            .is_some_and(|target| target.size() != size)
+        {
+            self.targets.clear();
+        }
+
+        while self.targets.len() < 2 {
+            self.targets.push(RenderTarget::new(
+                wgpu,
+                &self.bind_group_layouts,
+                self.format,
+                size,
+            ));
+        }
+    }
+
+    pub fn scene_target(&self) -> &RenderTarget {
+        &self.targets[0]
+    }
+
+    pub fn run(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        sampler_bind_group: &wgpu::BindGroup,
+        output: PostProcessOutput,
+    ) {
+        let mut passes = self.passes.iter().filter(|pass| pass.enabled()).peekable();
+        let mut input_index = 0;
+
+        while let Some(post_process_pass) = passes.next() {
+            let output_index = (input_index + 1) % self.targets.len();
+
+            if passes.peek().is_none() && output.viewport.is_none() {
+                let mut pass = begin_pass(encoder, output.view, output.clear_color);
+
+                post_process_pass.draw(
+                    &mut pass,
+                    self.targets[input_index].bind_group(),
+                    sampler_bind_group,
+                );
+
+                return;
+            }
+
+            let mut pass = begin_pass(encoder, self.targets[output_index].view(), Color::BLACK);
+
+            post_process_pass.draw(
+                &mut pass,
+                self.targets[input_index].bind_group(),
+                sampler_bind_group,
+            );
+
+            input_index = output_index;
+        }
+
+        let mut pass = begin_pass(encoder, output.view, output.clear_color);
+
+        if let Some(viewport) = output.viewport {
+            if viewport.w <= 0.0 || viewport.h <= 0.0 {
+                return;
+            }
+
+            pass.set_viewport(viewport.x, viewport.y, viewport.w, viewport.h, 0.0, 1.0);
+        }
+
+        self.blit_pass.draw(
+            &mut pass,
+            self.targets[input_index].bind_group(),
+            output.sampler_bind_group,
+        );
+    }
+}
+
+fn begin_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    view: &'a wgpu::TextureView,
+    clear_color: Color,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("post_process_pass"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(clear_color.into()),
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    })
+}
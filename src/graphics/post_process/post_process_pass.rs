@@ -0,0 +1,206 @@
+use crate::game::GameResult;
+use crate::graphics::{GraphicsContext, SharedBindGroupLayouts, WgpuContext};
+use anyhow::{anyhow, ensure};
+use bytemuck::Pod;
+use std::mem;
+use wgpu::util::DeviceExt;
+
+const PRELUDE: &str = include_str!("../shaders/post_process/prelude.wgsl");
+const MIN_UNIFORMS_SIZE: usize = 16;
+
+#[derive(Debug)]
+pub struct PostProcessPass {
+    pipeline: wgpu::RenderPipeline,
+    uniforms: Vec<u8>,
+    uniform_buffer: wgpu::Buffer,
+    uniform_bind_group: wgpu::BindGroup,
+    extra_bind_group: Option<wgpu::BindGroup>,
+    enabled: bool,
+}
+
+impl PostProcessPass {
+    pub fn new<G, U>(graphics: G, shader_source: &str, uniforms: &U) -> GameResult<Self>
+    where
+        G: AsRef<GraphicsContext>,
+        U: Pod,
+    {
+        let graphics = graphics.as_ref();
+        let device = graphics.device();
+
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+        let pass = Self::create(
+            &graphics.wgpu,
+            &graphics.bind_group_layouts,
+            graphics.surface_format(),
+            "custom",
+            shader_source,
+            bytemuck::bytes_of(uniforms),
+            None,
+        );
+
+        match pollster::block_on(device.pop_error_scope()) {
+            Some(error) => Err(anyhow!("Failed to create post-process pass: {error}")),
+            None => Ok(pass),
+        }
+    }
+
+    pub(crate) fn create(
+        wgpu: &WgpuContext,
+        bind_group_layouts: &SharedBindGroupLayouts,
+        format: wgpu::TextureFormat,
+        label: &str,
+        shader_source: &str,
+        uniforms: &[u8],
+        extra_bind_group: Option<(&wgpu::BindGroupLayout, wgpu::BindGroup)>,
+    ) -> Self {
+        let device = wgpu.device();
+
+        let mut padded_uniforms = uniforms.to_vec();
+        let padded_len = padded_uniforms
+            .len()
+            .max(MIN_UNIFORMS_SIZE)
+            .next_multiple_of(16);
+        padded_uniforms.resize(padded_len, 0);
+
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("post_process_uniform_buffer"),
+            contents: &padded_uniforms,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("post_process_uniform_bind_group"),
+            layout: bind_group_layouts.uniform(),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let mut layouts = vec![
+            bind_group_layouts.texture(),
+            bind_group_layouts.sampler(),
+            bind_group_layouts.uniform(),
+        ];
+
+        let extra_bind_group = extra_bind_group.map(|(layout, bind_group)| {
+            layouts.push(layout);
+            bind_group
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("post_process_pipeline_layout"),
+            bind_group_layouts: &layouts,
+            push_constant_ranges: &[],
+        });
+
+        let shader_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(&format!("{label}_post_process_shader")),
+            source: wgpu::ShaderSource::Wgsl(format!("{PRELUDE}\n{shader_source}").into()),
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(&format!("{label}_post_process_pipeline")),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader_module,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader_module,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            multiview: None,
+        });
+
+        Self {
+            pipeline,
+            uniforms: padded_uniforms,
+            uniform_buffer,
+            uniform_bind_group,
+            extra_bind_group,
+            enabled: true,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn uniforms<U>(&self) -> GameResult<U>
+    where
+        U: Pod,
+    {
+        let size = mem::size_of::<U>();
+
+        ensure!(
+            size <= self.uniforms.len(),
+            "Uniforms of {size} bytes are larger than the {} bytes the pass was created with",
+            self.uniforms.len(),
+        );
+
+        Ok(bytemuck::pod_read_unaligned(&self.uniforms[..size]))
+    }
+
+    pub fn set_uniforms<W, U>(&mut self, wgpu: W, uniforms: &U) -> GameResult<()>
+    where
+        W: AsRef<WgpuContext>,
+        U: Pod,
+    {
+        let uniforms = bytemuck::bytes_of(uniforms);
+
+        ensure!(
+            uniforms.len() <= self.uniforms.len(),
+            "Uniforms of {} bytes are larger than the {} bytes the pass was created with",
+            uniforms.len(),
+            self.uniforms.len(),
+        );
+
+        self.uniforms[..uniforms.len()].copy_from_slice(uniforms);
+        wgpu.as_ref()
+            .queue()
+            .write_buffer(&self.uniform_buffer, 0, &self.uniforms);
+
+        Ok(())
+    }
+
+    pub(crate) fn draw<'a>(
+        &'a self,
+        pass: &mut wgpu::RenderPass<'a>,
+        input_bind_group: &'a wgpu::BindGroup,
+        sampler_bind_group: &'a wgpu::BindGroup,
+    ) {
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, input_bind_group, &[]);
+        pass.set_bind_group(1, sampler_bind_group, &[]);
+        pass.set_bind_group(2, &self.uniform_bind_group, &[]);
+
+        if let Some(extra_bind_group) = self.extra_bind_group.as_ref() {
+            pass.set_bind_group(3, extra_bind_group, &[]);
+        }
+
+        pass.draw(0..3, 0..1);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::graphics::post_process::PostProcessPass;
+use crate::graphics::{Color, GraphicsContext};
+use bytemuck::{Pod, Zeroable};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Vignette {
+    pub color: Color,
+    pub intensity: f32,
+    pub radius: f32,
+    pub smoothness: f32,
+    pub _padding: f32,
+}
+
+impl Default for Vignette {
+    fn default() -> Self {
+        Self {
+            color: Color::BLACK,
+            intensity: 1.0,
+            radius: 0.5,
+            smoothness: 0.5,
+            _padding: 0.0,
+        }
+    }
+}
+
+impl PostProcessPass {
+    pub fn vignette<G>(graphics: G, vignette: Vignette) -> Self
+    where
+        G: AsRef<GraphicsContext>,
+    {
+        let graphics = graphics.as_ref();
+
+        Self::create(
+            &graphics.wgpu,
+            &graphics.bind_group_layouts,
+            graphics.surface_format(),
+            "vignette",
+            include_str!("../shaders/post_process/vignette.wgsl"),
+            bytemuck::bytes_of(&vignette),
+            None,
+        )
+    }
+}
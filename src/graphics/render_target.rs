@@ -0,0 +1,69 @@
+use crate::graphics::{SharedBindGroupLayouts, WgpuContext};
+use glam::UVec2;
+
+#[derive(Debug)]
+pub struct RenderTarget {
+    view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+    size: UVec2,
+}
+
+impl RenderTarget {
+    pub fn new<S>(
+        wgpu: &WgpuContext,
+        bind_group_layouts: &SharedBindGroupLayouts,
+        format: wgpu::TextureFormat,
+        size: S,
+    ) -> Self
+    where
+        S: Into<UVec2>,
+    {
+        let size = size.into().max(UVec2::ONE);
+
+        let view = wgpu
+            .device()
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some("render_target_texture"),
+                size: wgpu::Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            })
+            .create_view(&Default::default());
+
+        let bind_group = wgpu.device().create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("render_target_bind_group"),
+            layout: bind_group_layouts.texture(),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&view),
+            }],
+        });
+
+        Self {
+            view,
+            bind_group,
+            size,
+        }
+    }
+
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    pub fn bind_group(&self) -> &wgpu::BindGroup {
+        &self.bind_group
+    }
+
+    pub fn size(&self) -> UVec2 {
+        self.size
+    }
+}
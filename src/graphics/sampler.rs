@@ -0,0 +1,166 @@
+use crate::graphics::{SharedBindGroupLayouts, WgpuContext};
+use rustc_hash::FxHashMap;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SamplerOptions {
+    pub address_mode_u: wgpu::AddressMode,
+    pub address_mode_v: wgpu::AddressMode,
+    pub mag_filter: wgpu::FilterMode,
+    pub min_filter: wgpu::FilterMode,
+    pub mipmap_filter: wgpu::FilterMode,
+    pub anisotropy: u16,
+    pub border_color: Option<wgpu::SamplerBorderColor>,
+}
+
+impl SamplerOptions {
+    pub const NEAREST: Self = Self {
+        address_mode_u: wgpu::AddressMode::ClampToEdge,
+        address_mode_v: wgpu::AddressMode::ClampToEdge,
+        mag_filter: wgpu::FilterMode::Nearest,
+        min_filter: wgpu::FilterMode::Nearest,
+        mipmap_filter: wgpu::FilterMode::Nearest,
+        anisotropy: 1,
+        border_color: None,
+    };
+
+    pub const LINEAR: Self = Self {
+        address_mode_u: wgpu::AddressMode::ClampToEdge,
+        address_mode_v: wgpu::AddressMode::ClampToEdge,
+        mag_filter: wgpu::FilterMode::Linear,
+        min_filter: wgpu::FilterMode::Linear,
+        mipmap_filter: wgpu::FilterMode::Linear,
+        anisotropy: 1,
+        border_color: None,
+    };
+
+    pub const fn address_mode(mut self, address_mode: wgpu::AddressMode) -> Self {
+        self.address_mode_u = address_mode;
+        self.address_mode_v = address_mode;
+        self
+    }
+
+    pub const fn address_mode_u(mut self, address_mode: wgpu::AddressMode) -> Self {
+        self.address_mode_u = address_mode;
+        self
+    }
+
+    pub const fn address_mode_v(mut self, address_mode: wgpu::AddressMode) -> Self {
+        self.address_mode_v = address_mode;
+        self
+    }
+
+    pub const fn repeat(self) -> Self {
+        self.address_mode(wgpu::AddressMode::Repeat)
+    }
+
+    pub const fn mirror_repeat(self) -> Self {
+        self.address_mode(wgpu::AddressMode::MirrorRepeat)
+    }
+
+    pub const fn filter(mut self, filter: wgpu::FilterMode) -> Self {
+        self.mag_filter = filter;
+        self.min_filter = filter;
+        self
+    }
+
+    pub const fn mipmap_filter(mut self, filter: wgpu::FilterMode) -> Self {
+        self.mipmap_filter = filter;
+        self
+    }
+
+    pub const fn anisotropy(mut self, anisotropy: u16) -> Self {
+        self.anisotropy = anisotropy;
+        self
+    }
+
+    pub const fn border_color(mut self, border_color: wgpu::SamplerBorderColor) -> Self {
+        self.border_color = Some(border_color);
+        self
+    }
+
+    fn to_descriptor(self, border_supported: bool) -> wgpu::SamplerDescriptor<'static> {
+        let convert_address_mode = |address_mode| {
+            match address_mode {
+                wgpu::AddressMode::ClampToBorder if !border_supported => {
+                    wgpu::AddressMode::ClampToEdge
+                }
+                _ => address_mode,
+            }
+        };
+
+        let linear = self.mag_filter == wgpu::FilterMode::Linear
+            && self.min_filter == wgpu::FilterMode::Linear
+            && self.mipmap_filter == wgpu::FilterMode::Linear;
+
+        let anisotropy_clamp = if linear {
+            self.anisotropy.clamp(1, 16)
+        } else {
+            1
+        };
+
+        wgpu::SamplerDescriptor {
+            label: Some("sampler"),
+            address_mode_u: convert_address_mode(self.address_mode_u),
+            address_mode_v: convert_address_mode(self.address_mode_v),
+            mag_filter: self.mag_filter,
+            min_filter: self.min_filter,
+            mipmap_filter: self.mipmap_filter,
+            anisotropy_clamp,
+            border_color: self.border_color.filter(|_| border_supported),
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for SamplerOptions {
+    fn default() -> Self {
+        Self::NEAREST
+    }
+}
+
+#[derive(Debug)]
+pub struct SamplerCache {
+    bind_group_layouts: SharedBindGroupLayouts,
+    bind_groups: FxHashMap<SamplerOptions, wgpu::BindGroup>,
+}
+
+impl SamplerCache {
+    pub fn new(wgpu: &WgpuContext, bind_group_layouts: SharedBindGroupLayouts) -> Self {
+        let mut cache = Self {
+            bind_group_layouts,
+            bind_groups: Default::default(),
+        };
+
+        cache.insert(wgpu, SamplerOptions::NEAREST);
+        cache.insert(wgpu, SamplerOptions::LINEAR);
+        cache
+    }
+
+    pub fn insert(&mut self, wgpu: &WgpuContext, options: SamplerOptions) {
+        if self.bind_groups.contains_key(&options) {
+            return;
+        }
+
+        let device = wgpu.device();
+        let border_supported = device
+            .features()
+            .contains(wgpu::Features::ADDRESS_MODE_CLAMP_TO_BORDER);
+
+        let sampler = device.create_sampler(&options.to_descriptor(border_supported));
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("sampler_bind_group"),
+            layout: self.bind_group_layouts.sampler(),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Sampler(&sampler),
+            }],
+        });
+
+        self.bind_groups.insert(options, bind_group);
+    }
+
+    pub(crate) fn get(&self, options: &SamplerOptions) -> &wgpu::BindGroup {
+        &self.bind_groups[options]
+    }
+}
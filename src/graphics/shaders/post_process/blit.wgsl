@@ -0,0 +1,4 @@
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(input_texture, input_sampler, input.uv_coords);
+}
@@ -0,0 +1,35 @@
+struct Bloom {
+    threshold: f32,
+    intensity: f32,
+    radius: f32,
+}
+
+@group(2) @binding(0)
+var<uniform> bloom: Bloom;
+
+const SAMPLE_COUNT: i32 = 12;
+
+fn bright_pass(uv_coords: vec2<f32>) -> vec3<f32> {
+    let color = textureSample(input_texture, input_sampler, uv_coords).rgb;
+    let brightness = max(color.r, max(color.g, color.b));
+    let contribution = max(brightness - bloom.threshold, 0.0) / max(brightness, 0.0001);
+    return color * contribution;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(input_texture, input_sampler, input.uv_coords);
+    let texel_size = 1.0 / vec2<f32>(textureDimensions(input_texture));
+
+    var glow = bright_pass(input.uv_coords);
+
+    for (var i = 0; i < SAMPLE_COUNT; i += 1) {
+        let angle = f32(i) * 6.28318531 / f32(SAMPLE_COUNT);
+        let direction = vec2<f32>(cos(angle), sin(angle)) * texel_size * bloom.radius;
+        glow += bright_pass(input.uv_coords + direction);
+        glow += bright_pass(input.uv_coords + direction * 0.5);
+    }
+
+    glow /= f32(SAMPLE_COUNT * 2 + 1);
+    return vec4<f32>(sample.rgb + glow * bloom.intensity, sample.a);
+}
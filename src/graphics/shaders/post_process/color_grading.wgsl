@@ -0,0 +1,24 @@
+struct ColorGrading {
+    intensity: f32,
+}
+
+@group(2) @binding(0)
+var<uniform> color_grading: ColorGrading;
+
+@group(3) @binding(0)
+var lut_texture: texture_3d<f32>;
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(input_texture, input_sampler, input.uv_coords);
+
+    let lut_size = f32(textureDimensions(lut_texture).x);
+    let lut_scale = (lut_size - 1.0) / lut_size;
+    let lut_offset = 0.5 / lut_size;
+
+    let srgb = clamp(linear_to_srgb(sample.rgb), vec3<f32>(0.0), vec3<f32>(1.0));
+    let lut_coords = srgb * lut_scale + lut_offset;
+    let graded = srgb_to_linear(textureSample(lut_texture, input_sampler, lut_coords).rgb);
+
+    return vec4<f32>(mix(sample.rgb, graded, color_grading.intensity), sample.a);
+}
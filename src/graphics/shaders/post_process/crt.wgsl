@@ -0,0 +1,32 @@
+struct Crt {
+    curvature: f32,
+    scanline_intensity: f32,
+    scanline_count: f32,
+    aberration: f32,
+}
+
+@group(2) @binding(0)
+var<uniform> crt: Crt;
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let centered = input.uv_coords * 2.0 - 1.0;
+    let offset = centered.yx * centered.yx * crt.curvature;
+    let curved = centered + centered * offset;
+    let uv_coords = curved * 0.5 + 0.5;
+
+    let texel_size = 1.0 / vec2<f32>(textureDimensions(input_texture));
+    let aberration = vec2<f32>(crt.aberration * texel_size.x, 0.0);
+
+    let sample = textureSample(input_texture, input_sampler, uv_coords);
+    let red = textureSample(input_texture, input_sampler, uv_coords + aberration).r;
+    let blue = textureSample(input_texture, input_sampler, uv_coords - aberration).b;
+
+    let scanline = sin(uv_coords.y * crt.scanline_count * 3.14159265) * 0.5 + 0.5;
+    let darkening = 1.0 - crt.scanline_intensity * (1.0 - scanline);
+
+    let inside = all(uv_coords >= vec2<f32>(0.0)) && all(uv_coords <= vec2<f32>(1.0));
+    let color = vec3<f32>(red, sample.g, blue) * darkening;
+
+    return select(vec4<f32>(0.0, 0.0, 0.0, 1.0), vec4<f32>(color, sample.a), inside);
+}
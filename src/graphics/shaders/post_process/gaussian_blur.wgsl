@@ -0,0 +1,31 @@
+struct GaussianBlur {
+    direction: vec2<f32>,
+    radius: f32,
+    sigma: f32,
+}
+
+@group(2) @binding(0)
+var<uniform> blur: GaussianBlur;
+
+const MAX_RADIUS: i32 = 32;
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let texel_size = 1.0 / vec2<f32>(textureDimensions(input_texture));
+    let step = normalize(blur.direction) * texel_size;
+    let radius = min(i32(blur.radius), MAX_RADIUS);
+    let sigma = max(blur.sigma, 0.0001);
+
+    var color = vec4<f32>(0.0);
+    var total_weight = 0.0;
+
+    for (var i = -radius; i <= radius; i += 1) {
+        let x = f32(i);
+        let weight = exp(-(x * x) / (2.0 * sigma * sigma));
+        let uv_coords = input.uv_coords + step * x;
+        color += textureSampleLevel(input_texture, input_sampler, uv_coords, 0.0) * weight;
+        total_weight += weight;
+    }
+
+    return color / total_weight;
+}
@@ -0,0 +1,36 @@
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv_coords: vec2<f32>,
+}
+
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+
+@group(1) @binding(0)
+var input_sampler: sampler;
+
+@vertex
+fn vs_main(@builtin(vertex_index) i: u32) -> VertexOutput {
+    let uv_coords = vec2<f32>(f32((i << 1u) & 2u), f32(i & 2u));
+
+    let clip_position = vec4<f32>(
+        uv_coords.x * 2.0 - 1.0,
+        1.0 - uv_coords.y * 2.0,
+        0.0,
+        1.0,
+    );
+
+    return VertexOutput(clip_position, uv_coords);
+}
+
+fn srgb_to_linear(color: vec3<f32>) -> vec3<f32> {
+    let low = color / 12.92;
+    let high = pow((color + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, color <= vec3<f32>(0.04045));
+}
+
+fn linear_to_srgb(color: vec3<f32>) -> vec3<f32> {
+    let low = color * 12.92;
+    let high = 1.055 * pow(color, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, color <= vec3<f32>(0.0031308));
+}
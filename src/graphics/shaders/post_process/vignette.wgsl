@@ -0,0 +1,22 @@
+struct Vignette {
+    color: vec4<f32>,
+    intensity: f32,
+    radius: f32,
+    smoothness: f32,
+}
+
+@group(2) @binding(0)
+var<uniform> vignette: Vignette;
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(input_texture, input_sampler, input.uv_coords);
+
+    let distance = length(input.uv_coords - vec2<f32>(0.5, 0.5)) * 1.41421356;
+    let amount = smoothstep(vignette.radius, vignette.radius + vignette.smoothness, distance)
+        * vignette.intensity
+        * vignette.color.a;
+
+    let color = srgb_to_linear(vignette.color.rgb);
+    return vec4<f32>(mix(sample.rgb, color, amount), sample.a);
+}
@@ -7,6 +7,21 @@ struct VertexInput {
     @location(5) texture_size: vec2<f32>,
     @location(6) uv_edges: vec4<f32>,
     @location(7) linear_color: vec4<f32>,
+    @location(8) top_left_linear_color: vec4<f32>,
+    @location(9) bottom_left_linear_color: vec4<f32>,
+    @location(10) top_right_linear_color: vec4<f32>,
+    @location(11) bottom_right_linear_color: vec4<f32>,
+}
+
+struct GridVertexInput {
+    @location(0) position: vec2<f32>,
+    @location(1) uv_coords: vec2<f32>,
+    @location(2) vertex_linear_color: vec4<f32>,
+    @location(3) scale_rotation_x_axis: vec2<f32>,
+    @location(4) scale_rotation_y_axis: vec2<f32>,
+    @location(5) translation: vec2<f32>,
+    @location(6) anchor_offset: vec2<f32>,
+    @location(7) linear_color: vec4<f32>,
 }
 
 struct VertexOutput {
@@ -58,7 +73,33 @@ fn vs_main(@builtin(vertex_index) i: u32, input: VertexInput) -> VertexOutput {
         input.uv_edges[uv_indexes.y] / input.texture_size.y,
     );
 
-    return VertexOutput(clip_position, uv_coords, input.linear_color);
+    var corner_linear_colors = array<vec4<f32>, 4>(
+        input.top_left_linear_color,
+        input.bottom_left_linear_color,
+        input.top_right_linear_color,
+        input.bottom_right_linear_color,
+    );
+
+    let linear_color = input.linear_color * corner_linear_colors[i];
+
+    return VertexOutput(clip_position, uv_coords, linear_color);
+}
+
+@vertex
+fn vs_grid(input: GridVertexInput) -> VertexOutput {
+    let scale_rotation = mat2x2<f32>(
+        input.scale_rotation_x_axis,
+        input.scale_rotation_y_axis,
+    );
+
+    let position = scale_rotation
+        * (input.position - input.anchor_offset)
+        + input.translation;
+
+    let clip_position = projection * vec4<f32>(position, 0.0, 1.0);
+    let linear_color = input.linear_color * input.vertex_linear_color;
+
+    return VertexOutput(clip_position, input.uv_coords, linear_color);
 }
 
 @fragment
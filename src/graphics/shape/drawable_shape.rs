@@ -1,10 +1,16 @@
-use crate::graphics::shape::{Shape, ShapeInstance};
-use crate::graphics::{impl_drawable_methods, AsDrawable, Canvas, Color, Drawable, Transform};
-use glam::Vec2;
+use crate::graphics::shape::{RayHit, Shape, ShapeInstance};
+use crate::graphics::sprite::Texture;
+use crate::graphics::{
+    impl_drawable_methods, AsDrawable, Bounds, Canvas, Color, Drawable, SamplerOptions,
+    Transform,
+};
+use glam::{Affine2, Vec2};
 
 #[derive(Clone, Debug)]
 pub struct DrawableShape<'a> {
     pub shape: &'a Shape,
+    pub texture: Option<&'a Texture>,
+    pub sampler: SamplerOptions,
     pub transform: Transform,
     pub anchor_offset: Vec2,
     pub color: Color,
@@ -16,12 +22,86 @@ impl<'a> DrawableShape<'a> {
     pub fn new(shape: &'a Shape) -> Self {
         Self {
             shape,
+            texture: None,
+            sampler: SamplerOptions::NEAREST,
             transform: Transform::IDENTITY,
             anchor_offset: Vec2::ZERO,
             color: Color::WHITE,
         }
     }
 
+    pub fn texture(mut self, texture: &'a Texture) -> Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    pub fn smooth(mut self, smooth: bool) -> Self {
+        self.sampler = if smooth {
+            SamplerOptions::LINEAR
+        } else {
+            SamplerOptions::NEAREST
+        };
+
+        self
+    }
+
+    pub fn sampler(mut self, sampler: SamplerOptions) -> Self {
+        self.sampler = sampler;
+        self
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        let affine2 = self.to_affine2();
+        let bounds = self.shape.bounds();
+
+        let corners = [
+            bounds.top_left(),
+            Vec2::new(bounds.x + bounds.w, bounds.y),
+            bounds.bottom_right(),
+            Vec2::new(bounds.x, bounds.y + bounds.h),
+        ]
+        .map(|corner| affine2.transform_point2(corner));
+
+        let (min, max) = corners.iter().fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(min, max), &corner| (min.min(corner), max.max(corner)),
+        );
+
+        Bounds::from_min_max(min, max)
+    }
+
+    pub fn contains<P>(&self, point: P) -> bool
+    where
+        P: Into<Vec2>,
+    {
+        self.to_inverse_affine2()
+            .is_some_and(|inverse| self.shape.contains(inverse.transform_point2(point.into())))
+    }
+
+    pub fn raycast<O, D>(&self, origin: O, direction: D) -> Option<RayHit>
+    where
+        O: Into<Vec2>,
+        D: Into<Vec2>,
+    {
+        let origin = origin.into();
+        let affine2 = self.to_affine2();
+        let inverse = self.to_inverse_affine2()?;
+
+        let hit = self.shape.raycast(
+            inverse.transform_point2(origin),
+            inverse.transform_vector2(direction.into()),
+        )?;
+
+        let point = affine2.transform_point2(hit.point);
+        let normal = inverse.matrix2.transpose() * hit.normal;
+
+        Some(RayHit {
+            point,
+            normal: normal.normalize_or_zero(),
+            distance: point.distance(origin),
+        })
+    }
+
     pub fn to_shape_instance(&self) -> ShapeInstance {
         let affine2 = self.transform.to_affine2();
 
@@ -33,11 +113,35 @@ impl<'a> DrawableShape<'a> {
             linear_color: self.color.to_linear_vec4(),
         }
     }
+
+    fn to_affine2(&self) -> Affine2 {
+        self.transform.to_affine2() * Affine2::from_translation(-self.anchor_offset)
+    }
+
+    fn to_inverse_affine2(&self) -> Option<Affine2> {
+        let affine2 = self.to_affine2();
+
+        if affine2.matrix2.determinant().abs() <= f32::EPSILON {
+            return None;
+        }
+
+        Some(affine2.inverse())
+    }
 }
 
 impl Drawable for DrawableShape<'_> {
     fn draw(self, canvas: &mut Canvas) {
-        canvas.draw_shape(self.shape, self.to_shape_instance());
+        match self.texture {
+            Some(texture) => {
+                canvas.draw_textured_shape(
+                    self.shape,
+                    texture,
+                    self.sampler,
+                    self.to_shape_instance(),
+                );
+            }
+            None => canvas.draw_shape(self.shape, self.to_shape_instance()),
+        }
     }
 }
 
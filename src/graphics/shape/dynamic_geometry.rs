@@ -0,0 +1,224 @@
+use crate::graphics::shape::ShapeVertex;
+use crate::graphics::{Bounds, Color};
+use glam::Vec2;
+use std::f32::consts::{PI, TAU};
+use std::ops::Range;
+
+const CIRCLE_TOLERANCE: f32 = 0.25;
+const MIN_CIRCLE_SEGMENTS: u32 = 8;
+const MAX_CIRCLE_SEGMENTS: u32 = 256;
+const MITER_LIMIT: f32 = 2.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DrawMode {
+    Fill,
+    Stroke(f32),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct DynamicShapeBatch {
+    pub indexes: Range<u32>,
+    pub instance: u32,
+}
+
+#[derive(Clone, Default, Debug)]
+pub(crate) struct DynamicGeometry {
+    pub vertexes: Vec<ShapeVertex>,
+    pub indexes: Vec<u32>,
+}
+
+impl DynamicGeometry {
+    pub fn clear(&mut self) {
+        self.vertexes.clear();
+        self.indexes.clear();
+    }
+
+    pub fn index_count(&self) -> u32 {
+        self.indexes.len() as u32
+    }
+
+    pub fn line(&mut self, from: Vec2, to: Vec2, thickness: f32, color: Color) {
+        self.stroke(&[from, to], false, thickness, color);
+    }
+
+    pub fn rect(&mut self, bounds: Bounds, mode: DrawMode, color: Color) {
+        let points = [
+            bounds.top_left(),
+            Vec2::new(bounds.x + bounds.w, bounds.y),
+            bounds.bottom_right(),
+            Vec2::new(bounds.x, bounds.y + bounds.h),
+        ];
+
+        self.polygon(&points, mode, color);
+    }
+
+    pub fn circle(&mut self, center: Vec2, radius: f32, mode: DrawMode, color: Color) {
+        let segments = circle_segments(radius);
+
+        let points = (0..segments)
+            .map(|segment| {
+                let angle = TAU * segment as f32 / segments as f32;
+                center + Vec2::from_angle(angle) * radius
+            })
+            .collect::<Vec<_>>();
+
+        self.polygon(&points, mode, color);
+    }
+
+    pub fn polygon(&mut self, points: &[Vec2], mode: DrawMode, color: Color) {
+        match mode {
+            DrawMode::Fill => self.fill(points, color),
+            DrawMode::Stroke(thickness) => self.stroke(points, true, thickness, color),
+        }
+    }
+
+    pub fn polyline(&mut self, points: &[Vec2], thickness: f32, color: Color) {
+        self.stroke(points, false, thickness, color);
+    }
+
+    fn fill(&mut self, points: &[Vec2], color: Color) {
+        if points.len() < 3 {
+            return;
+        }
+
+        let base = self.push_vertexes(points.iter().copied(), color);
+
+        for triangle in triangulate(points) {
+            self.indexes.extend(triangle.map(|index| base + index as u32));
+        }
+    }
+
+    fn stroke(&mut self, points: &[Vec2], closed: bool, thickness: f32, color: Color) {
+        let count = points.len();
+
+        if count < 2 {
+            return;
+        }
+
+        let half_thickness = thickness * 0.5;
+
+        let sides = (0..count).flat_map(|i| {
+            let point = points[i];
+            let prev = (closed || i > 0).then(|| points[(i + count - 1) % count]);
+            let next = (closed || i + 1 < count).then(|| points[(i + 1) % count]);
+
+            let prev_normal = prev.map(|prev| (point - prev).normalize_or_zero().perp());
+            let next_normal = next.map(|next| (next - point).normalize_or_zero().perp());
+
+            let offset = match (prev_normal, next_normal) {
+                (Some(prev_normal), Some(next_normal)) => {
+                    let miter = (prev_normal + next_normal)
+                        .try_normalize()
+                        .unwrap_or(next_normal);
+                    let scale = 1.0 / miter.dot(next_normal).max(1.0 / MITER_LIMIT);
+                    miter * half_thickness * scale
+                }
+                (Some(normal), None) | (None, Some(normal)) => normal * half_thickness,
+                (None, None) => Vec2::ZERO,
+            };
+
+            [point + offset, point - offset]
+        });
+
+        let base = self.push_vertexes(sides, color);
+        let segments = if closed { count } else { count - 1 };
+
+        for i in 0..segments {
+            let a = base + (i * 2) as u32;
+            let b = base + (((i + 1) % count) * 2) as u32;
+            self.indexes.extend_from_slice(&[a, a + 1, b, b, a + 1, b + 1]);
+        }
+    }
+
+    fn push_vertexes<I>(&mut self, positions: I, color: Color) -> u32
+    where
+        I: IntoIterator<Item = Vec2>,
+    {
+        let base = self.vertexes.len() as u32;
+        let linear_color = color.to_linear_vec4();
+
+        self.vertexes
+            .extend(positions.into_iter().map(|position| {
+                ShapeVertex {
+                    position,
+                    linear_color,
+                    ..Default::default()
+                }
+            }));
+
+        base
+    }
+}
+
+fn circle_segments(radius: f32) -> u32 {
+    let radius = radius.abs();
+
+    if radius <= CIRCLE_TOLERANCE {
+        return MIN_CIRCLE_SEGMENTS;
+    }
+
+    let angle = (1.0 - CIRCLE_TOLERANCE / radius).acos();
+
+    ((PI / angle).ceil() as u32).clamp(MIN_CIRCLE_SEGMENTS, MAX_CIRCLE_SEGMENTS)
+}
+
+fn triangulate(points: &[Vec2]) -> Vec<[usize; 3]> {
+    let area = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| a.perp_dot(*b))
+        .sum::<f32>();
+
+    let orientation = area.signum();
+    let mut remaining = (0..points.len()).collect::<Vec<_>>();
+    let mut triangles = Vec::with_capacity(points.len() - 2);
+
+    while remaining.len() > 3 {
+        let count = remaining.len();
+
+        let ear = (0..count).find(|&i| {
+            let [a, b, c] = [
+                remaining[(i + count - 1) % count],
+                remaining[i],
+                remaining[(i + 1) % count],
+            ];
+
+            let (pa, pb, pc) = (points[a], points[b], points[c]);
+
+            if (pb - pa).perp_dot(pc - pb) * orientation <= 0.0 {
+                return false;
+            }
+
+            !remaining.iter().any(|&other| {
+                other != a
+                    && other != b
+                    && other != c
+                    && in_triangle(points[other], pa, pb, pc, orientation)
+            })
+        });
+
+        let Some(ear) = ear else {
+            break;
+        };
+
+        triangles.push([
+            remaining[(ear + count - 1) % count],
+            remaining[ear],
+            remaining[(ear + 1) % count],
+        ]);
+
+        remaining.remove(ear);
+    }
+
+    for i in 1..(remaining.len() - 1) {
+        triangles.push([remaining[0], remaining[i], remaining[i + 1]]);
+    }
+
+    triangles
+}
+
+fn in_triangle(point: Vec2, a: Vec2, b: Vec2, c: Vec2, orientation: f32) -> bool {
+    (b - a).perp_dot(point - a) * orientation >= 0.0
+        && (c - b).perp_dot(point - b) * orientation >= 0.0
+        && (a - c).perp_dot(point - c) * orientation >= 0.0
+}
@@ -0,0 +1,358 @@
+use crate::graphics::shape::ShapeVertex;
+use crate::graphics::Color;
+use glam::{Vec2, Vec4};
+use rustc_hash::FxHashMap;
+
+const MAX_SUBDIVISION_DEPTH: u32 = 12;
+const COLOR_TOLERANCE: f32 = 2.0 / 255.0;
+
+#[derive(Clone, Copy, Debug)]
+pub struct GradientStop {
+    pub offset: f32,
+    pub color: Color,
+}
+
+impl GradientStop {
+    pub const fn new(offset: f32, color: Color) -> Self {
+        Self { offset, color }
+    }
+}
+
+impl From<(f32, Color)> for GradientStop {
+    fn from((offset, color): (f32, Color)) -> Self {
+        Self { offset, color }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GradientKind {
+    Linear { start: Vec2, end: Vec2 },
+    Radial { center: Vec2, radius: f32 },
+}
+
+#[derive(Clone, Debug)]
+pub struct Gradient {
+    pub kind: GradientKind,
+    pub stops: Vec<GradientStop>,
+}
+
+impl Gradient {
+    pub fn linear<S, E, I>(start: S, end: E, stops: I) -> Self
+    where
+        S: Into<Vec2>,
+        E: Into<Vec2>,
+        I: IntoIterator,
+        I::Item: Into<GradientStop>,
+    {
+        Self::new(
+            GradientKind::Linear {
+                start: start.into(),
+                end: end.into(),
+            },
+            stops,
+        )
+    }
+
+    pub fn radial<C, I>(center: C, radius: f32, stops: I) -> Self
+    where
+        C: Into<Vec2>,
+        I: IntoIterator,
+        I::Item: Into<GradientStop>,
+    {
+        Self::new(
+            GradientKind::Radial {
+                center: center.into(),
+                radius,
+            },
+            stops,
+        )
+    }
+
+    fn new<I>(kind: GradientKind, stops: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<GradientStop>,
+    {
+        let mut stops = stops.into_iter().map(Into::into).collect::<Vec<_>>();
+        stops.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+
+        Self { kind, stops }
+    }
+
+    pub fn offset_at<P>(&self, point: P) -> f32
+    where
+        P: Into<Vec2>,
+    {
+        let point = point.into();
+
+        match self.kind {
+            GradientKind::Linear { start, end } => {
+                let direction = end - start;
+                let length_squared = direction.length_squared();
+
+                if length_squared <= f32::EPSILON {
+                    return 0.0;
+                }
+
+                (point - start).dot(direction) / length_squared
+            }
+            GradientKind::Radial { center, radius } => {
+                if radius <= f32::EPSILON {
+                    return 1.0;
+                }
+
+                point.distance(center) / radius
+            }
+        }
+    }
+
+    fn linear_color_at_offset(&self, offset: f32) -> Vec4 {
+        let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) else {
+            return Vec4::ONE;
+        };
+
+        if offset <= first.offset {
+            return first.color.to_linear_vec4();
+        }
+
+        if offset >= last.offset {
+            return last.color.to_linear_vec4();
+        }
+
+        let next = self
+            .stops
+            .iter()
+            .position(|stop| stop.offset > offset)
+            .unwrap_or(self.stops.len() - 1);
+
+        let from = &self.stops[next - 1];
+        let to = &self.stops[next];
+        let t = (offset - from.offset) / (to.offset - from.offset);
+
+        from.color
+            .to_linear_vec4()
+            .lerp(to.color.to_linear_vec4(), t)
+    }
+
+    fn clamp_offset(&self, offset: f32) -> f32 {
+        match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => offset.clamp(first.offset, last.offset),
+            _ => 0.0,
+        }
+    }
+
+    fn max_color_slope(&self) -> f32 {
+        self.stops
+            .windows(2)
+            .map(|stops| {
+                let color_delta = stops[1].color.to_linear_vec4() - stops[0].color.to_linear_vec4();
+                let offset_delta = stops[1].offset - stops[0].offset;
+
+                if offset_delta <= f32::EPSILON {
+                    0.0
+                } else {
+                    color_delta.abs().max_element() / offset_delta
+                }
+            })
+            .fold(0.0, f32::max)
+    }
+
+    fn is_linear(&self) -> bool {
+        matches!(self.kind, GradientKind::Linear { .. })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct GradientVertex {
+    position: Vec2,
+    offset: f32,
+    uv: Vec2,
+    linear_color: Vec4,
+}
+
+impl GradientVertex {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        Self {
+            position: self.position.lerp(to.position, t),
+            offset: self.offset + (to.offset - self.offset) * t,
+            uv: self.uv.lerp(to.uv, t),
+            linear_color: self.linear_color.lerp(to.linear_color, t),
+        }
+    }
+}
+
+pub(crate) fn apply_gradient(
+    vertexes: &[ShapeVertex],
+    indexes: &[u32],
+    gradient: &Gradient,
+    tolerance: f32,
+) -> (Vec<ShapeVertex>, Vec<u32>) {
+    let mut mesh = GradientMesh {
+        gradient,
+        tolerance: tolerance.max(f32::EPSILON),
+        color_slope: gradient.max_color_slope(),
+        vertexes: Vec::new(),
+        indexes: Vec::new(),
+        vertex_ids: FxHashMap::default(),
+    };
+
+    for triangle in indexes.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| {
+            let vertex = vertexes[triangle[i] as usize];
+
+            GradientVertex {
+                position: vertex.position,
+                offset: gradient.offset_at(vertex.position),
+                uv: vertex.uv,
+                linear_color: vertex.linear_color,
+            }
+        });
+
+        mesh.subdivide(a, b, c, 0);
+    }
+
+    (mesh.vertexes, mesh.indexes)
+}
+
+struct GradientMesh<'a> {
+    gradient: &'a Gradient,
+    tolerance: f32,
+    color_slope: f32,
+    vertexes: Vec<ShapeVertex>,
+    indexes: Vec<u32>,
+    vertex_ids: FxHashMap<[u32; 2], u32>,
+}
+
+impl GradientMesh<'_> {
+    fn subdivide(&mut self, a: GradientVertex, b: GradientVertex, c: GradientVertex, depth: u32) {
+        if depth < MAX_SUBDIVISION_DEPTH {
+            for (from, to, other) in [(a, b, c), (b, c, a), (c, a, b)] {
+                if self.needs_split(from, to) {
+                    let mid = self.midpoint(from, to);
+                    self.subdivide(from, mid, other, depth + 1);
+                    self.subdivide(mid, to, other, depth + 1);
+                    return;
+                }
+            }
+        }
+
+        self.slice(a, b, c);
+    }
+
+    fn needs_split(&self, from: GradientVertex, to: GradientVertex) -> bool {
+        if self.gradient.is_linear() || from.position.distance(to.position) <= self.tolerance {
+            return false;
+        }
+
+        let gradient = self.gradient;
+        let offset = gradient.clamp_offset(gradient.offset_at((from.position + to.position) * 0.5));
+        let interpolated_offset =
+            (gradient.clamp_offset(from.offset) + gradient.clamp_offset(to.offset)) * 0.5;
+
+        (offset - interpolated_offset).abs() * self.color_slope > COLOR_TOLERANCE
+    }
+
+    fn midpoint(&self, from: GradientVertex, to: GradientVertex) -> GradientVertex {
+        let (from, to) = canonical_edge(from, to);
+        let vertex = from.lerp(to, 0.5);
+
+        GradientVertex {
+            offset: self.gradient.offset_at(vertex.position),
+            ..vertex
+        }
+    }
+
+    fn slice(&mut self, a: GradientVertex, b: GradientVertex, c: GradientVertex) {
+        let min_offset = a.offset.min(b.offset).min(c.offset);
+        let max_offset = a.offset.max(b.offset).max(c.offset);
+
+        let mut polygon = vec![a, b, c];
+
+        for stop in self.gradient.stops.iter() {
+            if stop.offset <= min_offset || stop.offset >= max_offset {
+                continue;
+            }
+
+            let (below, above) = split_polygon(&polygon, stop.offset);
+            self.add_polygon(&below);
+            polygon = above;
+        }
+
+        self.add_polygon(&polygon);
+    }
+
+    fn add_polygon(&mut self, polygon: &[GradientVertex]) {
+        if polygon.len() < 3 {
+            return;
+        }
+
+        let ids = polygon
+            .iter()
+            .map(|vertex| self.add_vertex(*vertex))
+            .collect::<Vec<_>>();
+
+        for i in 1..(ids.len() - 1) {
+            self.indexes.extend_from_slice(&[ids[0], ids[i], ids[i + 1]]);
+        }
+    }
+
+    fn add_vertex(&mut self, vertex: GradientVertex) -> u32 {
+        let key = [vertex.position.x.to_bits(), vertex.position.y.to_bits()];
+
+        *self.vertex_ids.entry(key).or_insert_with(|| {
+            self.vertexes.push(ShapeVertex {
+                position: vertex.position,
+                uv: vertex.uv,
+                linear_color: self.gradient.linear_color_at_offset(vertex.offset)
+                    * vertex.linear_color,
+            });
+
+            (self.vertexes.len() - 1) as u32
+        })
+    }
+}
+
+fn split_polygon(
+    polygon: &[GradientVertex],
+    offset: f32,
+) -> (Vec<GradientVertex>, Vec<GradientVertex>) {
+    let mut below = Vec::with_capacity(polygon.len() + 1);
+    let mut above = Vec::with_capacity(polygon.len() + 1);
+
+    for (i, &from) in polygon.iter().enumerate() {
+        let to = polygon[(i + 1) % polygon.len()];
+
+        if from.offset <= offset {
+            below.push(from);
+        }
+
+        if from.offset >= offset {
+            above.push(from);
+        }
+
+        if (from.offset < offset && to.offset > offset)
+            || (from.offset > offset && to.offset < offset)
+        {
+            let (from, to) = canonical_edge(from, to);
+            let t = (offset - from.offset) / (to.offset - from.offset);
+
+            let vertex = GradientVertex {
+                offset,
+                ..from.lerp(to, t)
+            };
+
+            below.push(vertex);
+            above.push(vertex);
+        }
+    }
+
+    (below, above)
+}
+
+fn canonical_edge(a: GradientVertex, b: GradientVertex) -> (GradientVertex, GradientVertex) {
+    if (a.position.x, a.position.y) <= (b.position.x, b.position.y) {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
@@ -1,21 +1,33 @@
 mod drawable_shape;
+mod dynamic_geometry;
 mod shape;
 
+#[cfg(feature = "shape-builder")]
+mod gradient;
 #[cfg(feature = "shape-builder")]
 mod shape_builder;
+#[cfg(feature = "svg")]
+mod svg;
 
 pub use self::drawable_shape::*;
+pub use self::dynamic_geometry::*;
 pub use self::shape::*;
 
+#[cfg(feature = "shape-builder")]
+pub use self::gradient::*;
 #[cfg(feature = "shape-builder")]
 pub use self::shape_builder::*;
+#[cfg(feature = "svg")]
+pub use self::svg::*;
 
-use crate::graphics::{vertex_attr_array, SharedBindGroupLayouts, WgpuContext};
+use crate::graphics::sprite::Texture;
+use crate::graphics::{
+    vertex_attr_array, write_buffer, SamplerOptions, SharedBindGroupLayouts, WgpuContext,
+};
 use bytemuck::{Pod, Zeroable};
 use glam::{Vec2, Vec4};
 use std::mem;
 use std::ops::Range;
-use wgpu::util::DeviceExt;
 
 #[repr(C)]
 #[non_exhaustive]
@@ -43,14 +55,20 @@ impl Default for ShapeInstance {
 #[derive(Clone, Debug)]
 pub struct ShapeBatch {
     pub shape: Shape,
+    pub texture: Option<Texture>,
+    pub sampler: SamplerOptions,
     pub instances: Range<u32>,
 }
 
 #[derive(Debug)]
 pub struct ShapeRenderer {
     pipeline: wgpu::RenderPipeline,
+    textured_pipeline: wgpu::RenderPipeline,
     instances: Vec<ShapeInstance>,
     instance_buffer: Option<wgpu::Buffer>,
+    dynamic_geometry: DynamicGeometry,
+    dynamic_vertex_buffer: Option<wgpu::Buffer>,
+    dynamic_index_buffer: Option<wgpu::Buffer>,
 }
 
 impl ShapeRenderer {
@@ -68,6 +86,17 @@ impl ShapeRenderer {
             push_constant_ranges: &[],
         });
 
+        let textured_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("textured_shape_pipeline_layout"),
+                bind_group_layouts: &[
+                    bind_group_layouts.projection(),
+                    bind_group_layouts.texture(),
+                    bind_group_layouts.sampler(),
+                ],
+                push_constant_ranges: &[],
+            });
+
         let shader_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("shape_shader"),
             source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/shape.wgsl").into()),
@@ -77,14 +106,28 @@ impl ShapeRenderer {
             device,
             &pipeline_layout,
             &shader_module,
+            "fs_main",
+            texture_format,
+            sample_count,
+        );
+
+        let textured_pipeline = Self::create_pipeline(
+            device,
+            &textured_pipeline_layout,
+            &shader_module,
+            "fs_textured",
             texture_format,
             sample_count,
         );
 
         Self {
             pipeline,
+            textured_pipeline,
             instances: Vec::new(),
             instance_buffer: None,
+            dynamic_geometry: DynamicGeometry::default(),
+            dynamic_vertex_buffer: None,
+            dynamic_index_buffer: None,
         }
     }
 
@@ -92,6 +135,7 @@ impl ShapeRenderer {
         device: &wgpu::Device,
         pipeline_layout: &wgpu::PipelineLayout,
         shader_module: &wgpu::ShaderModule,
+        fragment_entry_point: &str,
         texture_format: wgpu::TextureFormat,
         sample_count: u32,
     ) -> wgpu::RenderPipeline {
@@ -105,18 +149,19 @@ impl ShapeRenderer {
                         step_mode: wgpu::VertexStepMode::Vertex,
                         attributes: &vertex_attr_array!(ShapeVertex {
                             0 => position: Float32x2,
-                            1 => linear_color: Float32x4,
+                            1 => uv: Float32x2,
+                            2 => linear_color: Float32x4,
                         }),
                     },
                     wgpu::VertexBufferLayout {
                         array_stride: mem::size_of::<ShapeInstance>() as _,
                         step_mode: wgpu::VertexStepMode::Instance,
                         attributes: &vertex_attr_array!(ShapeInstance {
-                            2 => scale_rotation_x_axis: Float32x2,
-                            3 => scale_rotation_y_axis: Float32x2,
-                            4 => translation: Float32x2,
-                            5 => anchor_offset: Float32x2,
-                            6 => linear_color: Float32x4,
+                            3 => scale_rotation_x_axis: Float32x2,
+                            4 => scale_rotation_y_axis: Float32x2,
+                            5 => translation: Float32x2,
+                            6 => anchor_offset: Float32x2,
+                            7 => linear_color: Float32x4,
                         }),
                     },
                 ],
@@ -140,7 +185,7 @@ impl ShapeRenderer {
             },
             fragment: Some(wgpu::FragmentState {
                 module: shader_module,
-                entry_point: "fs_main",
+                entry_point: fragment_entry_point,
                 targets: &[Some(wgpu::ColorTargetState {
                     format: texture_format,
                     blend: Some(wgpu::BlendState::ALPHA_BLENDING),
@@ -153,63 +198,114 @@ impl ShapeRenderer {
 
     pub fn begin(&mut self) {
         self.instances.clear();
+        self.dynamic_geometry.clear();
     }
 
     pub fn add(&mut self, instance: ShapeInstance) {
         self.instances.push(instance);
     }
 
+    pub(crate) fn dynamic_geometry(&mut self) -> &mut DynamicGeometry {
+        &mut self.dynamic_geometry
+    }
+
+    pub(crate) fn add_dynamic_instance(&mut self) -> u32 {
+        self.instances.push(ShapeInstance::default());
+        (self.instances.len() - 1) as u32
+    }
+
     pub fn end(&mut self, wgpu: &WgpuContext) {
-        if self.instances.is_empty() {
-            return;
-        }
+        write_buffer(
+            wgpu,
+            &mut self.instance_buffer,
+            "shape_instance_buffer",
+            wgpu::BufferUsages::VERTEX,
+            bytemuck::cast_slice(&self.instances),
+        );
 
-        let instances_size =
-            (self.instances.len() * mem::size_of::<ShapeInstance>()) as wgpu::BufferAddress;
-
-        match self.instance_buffer.as_ref() {
-            Some(instance_buffer) if instances_size <= instance_buffer.size() => {
-                wgpu.queue().write_buffer(
-                    instance_buffer,
-                    0,
-                    bytemuck::cast_slice(&self.instances),
-                );
-            }
-            _ => {
-                self.instance_buffer = Some(wgpu.device().create_buffer_init(
-                    &wgpu::util::BufferInitDescriptor {
-                        label: Some("shape_instance_buffer"),
-                        contents: bytemuck::cast_slice(&self.instances),
-                        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                    },
-                ));
-            }
-        }
+        write_buffer(
+            wgpu,
+            &mut self.dynamic_vertex_buffer,
+            "dynamic_shape_vertex_buffer",
+            wgpu::BufferUsages::VERTEX,
+            bytemuck::cast_slice(&self.dynamic_geometry.vertexes),
+        );
+
+        write_buffer(
+            wgpu,
+            &mut self.dynamic_index_buffer,
+            "dynamic_shape_index_buffer",
+            wgpu::BufferUsages::INDEX,
+            bytemuck::cast_slice(&self.dynamic_geometry.indexes),
+        );
     }
 
-    pub fn next_batch(&self, shape: Shape) -> ShapeBatch {
+    pub fn next_batch(
+        &self,
+        shape: Shape,
+        texture: Option<Texture>,
+        sampler: SamplerOptions,
+    ) -> ShapeBatch {
         let instance_count = self.instances.len() as u32;
         ShapeBatch {
             shape,
+            texture,
+            sampler,
             instances: instance_count..(instance_count + 1),
         }
     }
 
-    pub fn prepare_pipeline<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>) {
+    pub fn prepare_pipeline<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>, textured: bool) {
         let Some(instance_buffer) = self.instance_buffer.as_ref() else {
             return;
         };
 
-        pass.set_pipeline(&self.pipeline);
+        if textured {
+            pass.set_pipeline(&self.textured_pipeline);
+        } else {
+            pass.set_pipeline(&self.pipeline);
+        }
+
         pass.set_vertex_buffer(1, instance_buffer.slice(..));
     }
 
-    pub fn draw<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>, batch: &'a ShapeBatch) {
+    pub fn draw<'a>(
+        &'a self,
+        pass: &mut wgpu::RenderPass<'a>,
+        batch: &'a ShapeBatch,
+        texture_bind_groups: Option<(&'a wgpu::BindGroup, &'a wgpu::BindGroup)>,
+    ) {
+        if let Some((texture_bind_group, sampler_bind_group)) = texture_bind_groups {
+            pass.set_bind_group(1, texture_bind_group, &[]);
+            pass.set_bind_group(2, sampler_bind_group, &[]);
+        }
+
         pass.set_vertex_buffer(0, batch.shape.vertex_buffer().slice(..));
         pass.set_index_buffer(
             batch.shape.index_buffer().slice(..),
-            wgpu::IndexFormat::Uint16,
+            batch.shape.index_format(),
         );
         pass.draw_indexed(0..batch.shape.index_count(), 0, batch.instances.clone());
     }
+
+    pub(crate) fn draw_dynamic<'a>(
+        &'a self,
+        pass: &mut wgpu::RenderPass<'a>,
+        batch: &DynamicShapeBatch,
+    ) {
+        let (Some(vertex_buffer), Some(index_buffer)) = (
+            self.dynamic_vertex_buffer.as_ref(),
+            self.dynamic_index_buffer.as_ref(),
+        ) else {
+            return;
+        };
+
+        pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        pass.draw_indexed(
+            batch.indexes.clone(),
+            0,
+            batch.instance..(batch.instance + 1),
+        );
+    }
 }
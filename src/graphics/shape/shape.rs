@@ -1,15 +1,15 @@
-use crate::graphics::WgpuContext;
+use crate::graphics::{Bounds, WgpuContext};
 use bytemuck::{Pod, Zeroable};
 use glam::{Vec2, Vec4};
+use std::borrow::Cow;
 use std::sync::Arc;
-use std::{fmt, mem};
 use wgpu::util::DeviceExt;
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct ShapeVertex {
     pub position: Vec2,
-    pub _padding: [f32; 2],
+    pub uv: Vec2,
     pub linear_color: Vec4,
 }
 
@@ -17,18 +17,38 @@ impl Default for ShapeVertex {
     fn default() -> Self {
         Self {
             position: Vec2::ZERO,
-            _padding: [0.0, 0.0],
+            uv: Vec2::ZERO,
             linear_color: Vec4::ONE,
         }
     }
 }
 
-impl fmt::Debug for ShapeVertex {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("ShapeVertex")
-            .field("position", &self.position)
-            .field("linear_color", &self.linear_color)
-            .finish()
+#[derive(Clone, Copy, Debug)]
+pub struct RayHit {
+    pub point: Vec2,
+    pub normal: Vec2,
+    pub distance: f32,
+}
+
+pub trait ShapeIndex: Pod {
+    const FORMAT: wgpu::IndexFormat;
+
+    fn to_u32(self) -> u32;
+}
+
+impl ShapeIndex for u16 {
+    const FORMAT: wgpu::IndexFormat = wgpu::IndexFormat::Uint16;
+
+    fn to_u32(self) -> u32 {
+        self as u32
+    }
+}
+
+impl ShapeIndex for u32 {
+    const FORMAT: wgpu::IndexFormat = wgpu::IndexFormat::Uint32;
+
+    fn to_u32(self) -> u32 {
+        self
     }
 }
 
@@ -36,34 +56,99 @@ impl fmt::Debug for ShapeVertex {
 struct ShapeData {
     vertex_buffer: wgpu::Buffer,
     index_buffer: wgpu::Buffer,
+    index_format: wgpu::IndexFormat,
+    vertexes: Vec<ShapeVertex>,
+    indexes: Vec<u32>,
+    bounds: Bounds,
 }
 
-#[derive(Clone, Debug)]
-pub struct Shape(Arc<ShapeData>);
-
-impl Shape {
-    pub fn new<W>(wgpu: W, vertexes: &[ShapeVertex], indexes: &[u16]) -> Self
+impl ShapeData {
+    fn new<I>(wgpu: &WgpuContext, vertexes: &[ShapeVertex], indexes: &[I]) -> Self
     where
-        W: AsRef<WgpuContext>,
+        I: ShapeIndex,
     {
-        let device = wgpu.as_ref().device();
+        let device = wgpu.device();
 
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("shape_vertex_buffer"),
             contents: bytemuck::cast_slice(vertexes),
-            usage: wgpu::BufferUsages::VERTEX,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
         });
 
         let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("shape_index_buffer"),
             contents: bytemuck::cast_slice(indexes),
-            usage: wgpu::BufferUsages::INDEX,
+            usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
         });
 
-        Self(Arc::new(ShapeData {
+        Self {
             vertex_buffer,
             index_buffer,
-        }))
+            index_format: I::FORMAT,
+            vertexes: vertexes.to_vec(),
+            indexes: indexes.iter().map(|index| index.to_u32()).collect(),
+            bounds: vertex_bounds(vertexes),
+        }
+    }
+
+    fn update<I>(&mut self, wgpu: &WgpuContext, vertexes: &[ShapeVertex], indexes: &[I]) -> bool
+    where
+        I: ShapeIndex,
+    {
+        let vertex_bytes = aligned_bytes(bytemuck::cast_slice(vertexes));
+        let index_bytes = aligned_bytes(bytemuck::cast_slice(indexes));
+
+        if vertex_bytes.len() as wgpu::BufferAddress > self.vertex_buffer.size()
+            || index_bytes.len() as wgpu::BufferAddress > self.index_buffer.size()
+        {
+            return false;
+        }
+
+        let queue = wgpu.queue();
+
+        if !vertex_bytes.is_empty() {
+            queue.write_buffer(&self.vertex_buffer, 0, &vertex_bytes);
+        }
+
+        if !index_bytes.is_empty() {
+            queue.write_buffer(&self.index_buffer, 0, &index_bytes);
+        }
+
+        self.index_format = I::FORMAT;
+        self.vertexes.clear();
+        self.vertexes.extend_from_slice(vertexes);
+        self.indexes.clear();
+        self.indexes.extend(indexes.iter().map(|index| index.to_u32()));
+        self.bounds = vertex_bounds(vertexes);
+        true
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Shape(Arc<ShapeData>);
+
+impl Shape {
+    pub fn new<W, I>(wgpu: W, vertexes: &[ShapeVertex], indexes: &[I]) -> Self
+    where
+        W: AsRef<WgpuContext>,
+        I: ShapeIndex,
+    {
+        Self(Arc::new(ShapeData::new(wgpu.as_ref(), vertexes, indexes)))
+    }
+
+    pub fn update<W, I>(&mut self, wgpu: W, vertexes: &[ShapeVertex], indexes: &[I])
+    where
+        W: AsRef<WgpuContext>,
+        I: ShapeIndex,
+    {
+        let wgpu = wgpu.as_ref();
+
+        let updated = Arc::get_mut(&mut self.0)
+            .is_some_and(|shape_data| shape_data.update(wgpu, vertexes, indexes));
+
+        if !updated {
+            self.0 = Arc::new(ShapeData::new(wgpu, vertexes, indexes));
+        }
     }
 
     pub fn vertex_buffer(&self) -> &wgpu::Buffer {
@@ -74,8 +159,65 @@ impl Shape {
         &self.0.index_buffer
     }
 
+    pub fn index_format(&self) -> wgpu::IndexFormat {
+        self.0.index_format
+    }
+
     pub fn index_count(&self) -> u32 {
-        (self.0.index_buffer.size() / (mem::size_of::<u16>() as wgpu::BufferAddress)) as u32
+        self.0.indexes.len() as u32
+    }
+
+    pub fn vertexes(&self) -> &[ShapeVertex] {
+        &self.0.vertexes
+    }
+
+    pub fn indexes(&self) -> &[u32] {
+        &self.0.indexes
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        self.0.bounds
+    }
+
+    pub fn contains<P>(&self, point: P) -> bool
+    where
+        P: Into<Vec2>,
+    {
+        let point = point.into();
+
+        self.0.bounds.contains(point)
+            && self
+                .triangles()
+                .any(|[a, b, c]| triangle_contains(point, a, b, c))
+    }
+
+    pub fn raycast<O, D>(&self, origin: O, direction: D) -> Option<RayHit>
+    where
+        O: Into<Vec2>,
+        D: Into<Vec2>,
+    {
+        let origin = origin.into();
+        let direction = direction.into().try_normalize()?;
+
+        if self.contains(origin) {
+            return Some(RayHit {
+                point: origin,
+                normal: -direction,
+                distance: 0.0,
+            });
+        }
+
+        self.triangles()
+            .flat_map(|[a, b, c]| [(a, b), (b, c), (c, a)])
+            .filter_map(|(from, to)| raycast_segment(origin, direction, from, to))
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+
+    fn triangles(&self) -> impl Iterator<Item = [Vec2; 3]> + '_ {
+        self.0
+            .indexes
+            .chunks_exact(3)
+            .map(|triangle| triangle_positions(&self.0.vertexes, triangle))
     }
 }
 
@@ -88,3 +230,64 @@ impl PartialEq for Shape {
 impl Eq for Shape {
     // Empty
 }
+
+pub(crate) fn vertex_bounds(vertexes: &[ShapeVertex]) -> Bounds {
+    if vertexes.is_empty() {
+        return Bounds::default();
+    }
+
+    let (min, max) = vertexes.iter().fold(
+        (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+        |(min, max), vertex| (min.min(vertex.position), max.max(vertex.position)),
+    );
+
+    Bounds::from_min_max(min, max)
+}
+
+fn triangle_positions(vertexes: &[ShapeVertex], triangle: &[u32]) -> [Vec2; 3] {
+    [0, 1, 2].map(|i| vertexes[triangle[i] as usize].position)
+}
+
+fn triangle_contains(point: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    let ab = (b - a).perp_dot(point - a);
+    let bc = (c - b).perp_dot(point - b);
+    let ca = (a - c).perp_dot(point - c);
+
+    (ab >= 0.0 && bc >= 0.0 && ca >= 0.0) || (ab <= 0.0 && bc <= 0.0 && ca <= 0.0)
+}
+
+fn raycast_segment(origin: Vec2, direction: Vec2, from: Vec2, to: Vec2) -> Option<RayHit> {
+    let edge = to - from;
+    let denominator = direction.perp_dot(edge);
+
+    if denominator.abs() <= f32::EPSILON {
+        return None;
+    }
+
+    let distance = (from - origin).perp_dot(edge) / denominator;
+    let edge_t = (from - origin).perp_dot(direction) / denominator;
+
+    if distance < 0.0 || !(0.0..=1.0).contains(&edge_t) {
+        return None;
+    }
+
+    let normal = edge.perp().normalize_or_zero();
+
+    Some(RayHit {
+        point: origin + direction * distance,
+        normal: if normal.dot(direction) > 0.0 { -normal } else { normal },
+        distance,
+    })
+}
+
+fn aligned_bytes(bytes: &[u8]) -> Cow<'_, [u8]> {
+    let alignment = wgpu::COPY_BUFFER_ALIGNMENT as usize;
+
+    if bytes.len().is_multiple_of(alignment) {
+        return Cow::Borrowed(bytes);
+    }
+
+    let mut aligned = bytes.to_vec();
+    aligned.resize(bytes.len().next_multiple_of(alignment), 0);
+    Cow::Owned(aligned)
+}
@@ -1,17 +1,35 @@
-use crate::graphics::shape::{Shape, ShapeVertex};
+use crate::game::GameResult;
+use crate::graphics::shape::{
+    apply_gradient, vertex_bounds, Gradient, GradientStop, RayHit, Shape, ShapeVertex,
+};
 use crate::graphics::{Bounds, Color, WgpuContext};
+use anyhow::Context;
 use glam::Vec2;
-use lyon::geom::Box2D;
+use lyon::algorithms::aabb::bounding_box;
+use lyon::algorithms::hit_test::hit_test_path;
+use lyon::algorithms::raycast::{raycast_path, Ray};
+use lyon::geom::{
+    Arc, ArcFlags, Box2D, CubicBezierSegment, LineSegment, QuadraticBezierSegment, SvgArc,
+};
 use lyon::math::{Angle, Point, Vector};
 use lyon::path::builder::BorderRadii as LyonBorderRadii;
 use lyon::path::path::BuilderWithAttributes;
+use lyon::path::iterator::PathIterator as _;
 use lyon::path::traits::PathBuilder as _;
-use lyon::path::{Path, Winding};
-use lyon::tessellation::{BuffersBuilder, FillTessellator, FillVertex, VertexBuffers};
+use lyon::path::{Event, Path, PathEvent, Polygon, Winding};
+use lyon::tessellation::{
+    BuffersBuilder, FillOptions, FillRule as LyonFillRule, FillTessellator, FillVertex,
+    LineCap as LyonLineCap, LineJoin as LyonLineJoin, StrokeOptions, StrokeTessellator,
+    StrokeVertex, VertexBuffers,
+};
+use std::f32::consts::{FRAC_PI_2, TAU};
 use std::mem;
+use std::ops::Range;
 
 type PathBuilder = BuilderWithAttributes;
 
+const ATTRIBUTE_COUNT: usize = 4;
+
 #[derive(Clone, Copy, Default, Debug)]
 pub struct BorderRadii {
     pub top_left: f32,
@@ -42,6 +60,60 @@ impl From<[f32; 4]> for BorderRadii {
     }
 }
 
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum LineCap {
+    #[default]
+    Butt,
+    Square,
+    Round,
+}
+
+impl From<LineCap> for LyonLineCap {
+    fn from(line_cap: LineCap) -> Self {
+        match line_cap {
+            LineCap::Butt => LyonLineCap::Butt,
+            LineCap::Square => LyonLineCap::Square,
+            LineCap::Round => LyonLineCap::Round,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum LineJoin {
+    #[default]
+    Miter,
+    MiterClip,
+    Round,
+    Bevel,
+}
+
+impl From<LineJoin> for LyonLineJoin {
+    fn from(line_join: LineJoin) -> Self {
+        match line_join {
+            LineJoin::Miter => LyonLineJoin::Miter,
+            LineJoin::MiterClip => LyonLineJoin::MiterClip,
+            LineJoin::Round => LyonLineJoin::Round,
+            LineJoin::Bevel => LyonLineJoin::Bevel,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    #[default]
+    EvenOdd,
+    NonZero,
+}
+
+impl From<FillRule> for LyonFillRule {
+    fn from(fill_rule: FillRule) -> Self {
+        match fill_rule {
+            FillRule::EvenOdd => LyonFillRule::EvenOdd,
+            FillRule::NonZero => LyonFillRule::NonZero,
+        }
+    }
+}
+
 impl Shape {
     #[inline]
     pub fn builder() -> ShapeBuilder {
@@ -49,10 +121,70 @@ impl Shape {
     }
 }
 
+#[derive(Clone, Debug)]
+struct PathStyle {
+    fill: bool,
+    fill_gradient: Option<Gradient>,
+    fill_rule: FillRule,
+    tolerance: f32,
+    stroke: bool,
+    stroke_width: f32,
+    stroke_color: Option<Color>,
+    stroke_gradient: Option<Gradient>,
+    uv_bounds: Option<Bounds>,
+    line_cap: LineCap,
+    line_join: LineJoin,
+    miter_limit: f32,
+    dashes: Vec<f32>,
+    dash_offset: f32,
+}
+
+impl PathStyle {
+    fn fill_options(&self) -> FillOptions {
+        FillOptions::default()
+            .with_fill_rule(self.fill_rule.into())
+            .with_tolerance(self.tolerance)
+    }
+
+    fn stroke_options(&self) -> StrokeOptions {
+        StrokeOptions::default()
+            .with_tolerance(self.tolerance)
+            .with_line_width(self.stroke_width)
+            .with_line_cap(self.line_cap.into())
+            .with_line_join(self.line_join.into())
+            .with_miter_limit(self.miter_limit.max(StrokeOptions::MINIMUM_MITER_LIMIT))
+    }
+}
+
+impl Default for PathStyle {
+    fn default() -> Self {
+        Self {
+            fill: true,
+            fill_gradient: None,
+            fill_rule: FillRule::default(),
+            tolerance: FillOptions::DEFAULT_TOLERANCE,
+            stroke: false,
+            stroke_width: StrokeOptions::DEFAULT_LINE_WIDTH,
+            stroke_color: None,
+            stroke_gradient: None,
+            uv_bounds: None,
+            line_cap: LineCap::default(),
+            line_join: LineJoin::default(),
+            miter_limit: StrokeOptions::DEFAULT_MITER_LIMIT,
+            dashes: Vec::new(),
+            dash_offset: 0.0,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct ShapeBuilder {
     path_builder: PathBuilder,
+    paths: Vec<(Path, PathStyle)>,
+    current_position: Option<Vec2>,
     active_color: Color,
+    active_style: PathStyle,
+    pending_style: Option<PathStyle>,
 }
 
 impl ShapeBuilder {
@@ -62,10 +194,121 @@ impl ShapeBuilder {
         self
     }
 
+    pub fn fill(&mut self) -> &mut Self {
+        self.update_style(|style| {
+            style.fill = true;
+            style.stroke = false;
+        })
+    }
+
+    pub fn stroke(&mut self, width: f32) -> &mut Self {
+        self.update_style(|style| {
+            style.fill = false;
+            style.stroke = true;
+            style.stroke_width = width;
+        })
+    }
+
+    pub fn fill_and_stroke(&mut self, width: f32) -> &mut Self {
+        self.update_style(|style| {
+            style.fill = true;
+            style.stroke = true;
+            style.stroke_width = width;
+        })
+    }
+
+    pub fn stroke_width(&mut self, width: f32) -> &mut Self {
+        self.update_style(|style| style.stroke_width = width)
+    }
+
+    pub fn stroke_color(&mut self, color: Color) -> &mut Self {
+        self.update_style(|style| style.stroke_color = Some(color))
+    }
+
+    pub fn fill_gradient(&mut self, gradient: Gradient) -> &mut Self {
+        self.update_style(|style| {
+            style.fill = true;
+            style.fill_gradient = Some(gradient);
+        })
+    }
+
+    pub fn fill_linear_gradient<S, E, I>(&mut self, start: S, end: E, stops: I) -> &mut Self
+    where
+        S: Into<Vec2>,
+        E: Into<Vec2>,
+        I: IntoIterator,
+        I::Item: Into<GradientStop>,
+    {
+        self.fill_gradient(Gradient::linear(start, end, stops))
+    }
+
+    pub fn fill_radial_gradient<C, I>(&mut self, center: C, radius: f32, stops: I) -> &mut Self
+    where
+        C: Into<Vec2>,
+        I: IntoIterator,
+        I::Item: Into<GradientStop>,
+    {
+        self.fill_gradient(Gradient::radial(center, radius, stops))
+    }
+
+    pub fn stroke_gradient(&mut self, gradient: Gradient) -> &mut Self {
+        self.update_style(|style| style.stroke_gradient = Some(gradient))
+    }
+
+    pub fn clear_gradients(&mut self) -> &mut Self {
+        self.update_style(|style| {
+            style.fill_gradient = None;
+            style.stroke_gradient = None;
+        })
+    }
+
+    pub fn uv_bounds<B>(&mut self, uv_bounds: B) -> &mut Self
+    where
+        B: Into<Bounds>,
+    {
+        let uv_bounds = uv_bounds.into();
+        self.update_style(|style| style.uv_bounds = Some(uv_bounds))
+    }
+
+    pub fn auto_uv_bounds(&mut self) -> &mut Self {
+        self.update_style(|style| style.uv_bounds = None)
+    }
+
+    pub fn line_cap(&mut self, line_cap: LineCap) -> &mut Self {
+        self.update_style(|style| style.line_cap = line_cap)
+    }
+
+    pub fn line_join(&mut self, line_join: LineJoin) -> &mut Self {
+        self.update_style(|style| style.line_join = line_join)
+    }
+
+    pub fn miter_limit(&mut self, miter_limit: f32) -> &mut Self {
+        self.update_style(|style| style.miter_limit = miter_limit)
+    }
+
+    pub fn dashes(&mut self, dashes: &[f32]) -> &mut Self {
+        self.update_style(|style| style.dashes = dashes.to_vec())
+    }
+
+    pub fn dash_offset(&mut self, dash_offset: f32) -> &mut Self {
+        self.update_style(|style| style.dash_offset = dash_offset)
+    }
+
+    pub fn fill_rule(&mut self, fill_rule: FillRule) -> &mut Self {
+        self.update_style(|style| style.fill_rule = fill_rule)
+    }
+
+    pub fn tolerance(&mut self, tolerance: f32) -> &mut Self {
+        self.update_style(|style| style.tolerance = tolerance)
+    }
+
     pub fn begin<P>(&mut self, point: P) -> &mut Self
     where
         P: Into<Vec2>,
     {
+        let point = point.into();
+        self.current_position = Some(point);
+
         self.path_builder
             .begin(convert_point(point), &self.attributes());
 
@@ -76,15 +319,227 @@ impl ShapeBuilder {
     where
         P: Into<Vec2>,
     {
+        let point = point.into();
+        self.current_position = Some(point);
+
         self.path_builder
             .line_to(convert_point(point), &self.attributes());
 
         self
     }
 
+    pub fn quadratic_bezier_to<C, P>(&mut self, ctrl: C, point: P) -> &mut Self
+    where
+        C: Into<Vec2>,
+        P: Into<Vec2>,
+    {
+        let point = point.into();
+        self.current_position = Some(point);
+
+        self.path_builder.quadratic_bezier_to(
+            convert_point(ctrl),
+            convert_point(point),
+            &self.attributes(),
+        );
+
+        self
+    }
+
+    pub fn cubic_bezier_to<C1, C2, P>(&mut self, ctrl1: C1, ctrl2: C2, point: P) -> &mut Self
+    where
+        C1: Into<Vec2>,
+        C2: Into<Vec2>,
+        P: Into<Vec2>,
+    {
+        let point = point.into();
+        self.current_position = Some(point);
+
+        self.path_builder.cubic_bezier_to(
+            convert_point(ctrl1),
+            convert_point(ctrl2),
+            convert_point(point),
+            &self.attributes(),
+        );
+
+        self
+    }
+
+    pub fn arc_to<R, P>(
+        &mut self,
+        radii: R,
+        x_rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        point: P,
+    ) -> &mut Self
+    where
+        R: Into<Vec2>,
+        P: Into<Vec2>,
+    {
+        let Some(from) = self.current_position else {
+            return self.begin(point);
+        };
+
+        let arc = SvgArc {
+            from: convert_point(from),
+            to: convert_point(point),
+            radii: convert_vector(radii),
+            x_rotation: Angle::radians(x_rotation),
+            flags: ArcFlags { large_arc, sweep },
+        };
+
+        if arc.is_straight_line() {
+            return self.line_to(arc.to.to_array());
+        }
+
+        arc.for_each_quadratic_bezier(&mut |segment| {
+            self.quadratic_bezier_to(segment.ctrl.to_array(), segment.to.to_array());
+        });
+
+        self
+    }
+
+    pub fn arc<C>(
+        &mut self,
+        center: C,
+        radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+    ) -> &mut Self
+    where
+        C: Into<Vec2>,
+    {
+        let arc = Arc {
+            center: convert_point(center),
+            radii: Vector::splat(radius),
+            start_angle: Angle::radians(start_angle),
+            sweep_angle: Angle::radians(sweep_angle),
+            x_rotation: Angle::zero(),
+        };
+
+        let from = arc.from().to_array();
+
+        if self.current_position.is_some() {
+            self.line_to(from);
+        } else {
+            self.begin(from);
+        }
+
+        arc.for_each_quadratic_bezier(&mut |segment| {
+            self.quadratic_bezier_to(segment.ctrl.to_array(), segment.to.to_array());
+        });
+
+        self
+    }
+
     #[inline]
     pub fn end(&mut self) -> &mut Self {
-        self.path_builder.end(true);
+        self.end_subpath(true)
+    }
+
+    #[inline]
+    pub fn end_open(&mut self) -> &mut Self {
+        self.end_subpath(false)
+    }
+
+    pub fn polygon(&mut self, points: &[Vec2]) -> &mut Self {
+        self.add_polygon(points, true)
+    }
+
+    pub fn polyline(&mut self, points: &[Vec2]) -> &mut Self {
+        self.add_polygon(points, false)
+    }
+
+    pub fn regular_polygon<C>(
+        &mut self,
+        center: C,
+        radius: f32,
+        sides: u32,
+        rotation: f32,
+    ) -> &mut Self
+    where
+        C: Into<Vec2>,
+    {
+        let center = center.into();
+        let sides = sides.max(3);
+
+        let points = (0..sides)
+            .map(|side| {
+                let angle = rotation - FRAC_PI_2 + TAU * side as f32 / sides as f32;
+                center + Vec2::from_angle(angle) * radius
+            })
+            .collect::<Vec<_>>();
+
+        self.polygon(&points)
+    }
+
+    pub fn star<C>(
+        &mut self,
+        center: C,
+        outer_radius: f32,
+        inner_radius: f32,
+        points: u32,
+        rotation: f32,
+    ) -> &mut Self
+    where
+        C: Into<Vec2>,
+    {
+        let center = center.into();
+        let vertex_count = points.max(2) * 2;
+
+        let points = (0..vertex_count)
+            .map(|vertex| {
+                let angle = rotation - FRAC_PI_2 + TAU * vertex as f32 / vertex_count as f32;
+                let radius = if vertex % 2 == 0 {
+                    outer_radius
+                } else {
+                    inner_radius
+                };
+
+                center + Vec2::from_angle(angle) * radius
+            })
+            .collect::<Vec<_>>();
+
+        self.polygon(&points)
+    }
+
+    pub fn pie<C>(
+        &mut self,
+        center: C,
+        radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+    ) -> &mut Self
+    where
+        C: Into<Vec2>,
+    {
+        let center = center.into();
+
+        self.begin(center)
+            .arc(center, radius, start_angle, sweep_angle)
+            .end()
+    }
+
+    pub fn ring<C>(&mut self, center: C, outer_radius: f32, inner_radius: f32) -> &mut Self
+    where
+        C: Into<Vec2>,
+    {
+        let center = convert_point(center);
+
+        self.path_builder.add_circle(
+            center,
+            outer_radius,
+            Winding::Positive,
+            &self.attributes(),
+        );
+
+        self.path_builder.add_circle(
+            center,
+            inner_radius,
+            Winding::Negative,
+            &self.attributes(),
+        );
+
         self
     }
 
@@ -163,26 +618,278 @@ impl ShapeBuilder {
         self
     }
 
-    pub fn build<W>(&mut self, wgpu: W) -> Shape
+    pub fn build<W>(&mut self, wgpu: W) -> GameResult<Shape>
     where
         W: AsRef<WgpuContext>,
     {
-        let path_builder = mem::replace(&mut self.path_builder, new_path_builder());
-        let path = path_builder.build();
+        let buffers = self.tessellate()?;
+
+        match u16_indexes(&buffers) {
+            Some(indexes) => Ok(Shape::new(wgpu, &buffers.vertices, &indexes)),
+            None => Ok(Shape::new(wgpu, &buffers.vertices, &buffers.indices)),
+        }
+    }
+
+    pub fn update_shape<W>(&mut self, wgpu: W, shape: &mut Shape) -> GameResult<()>
+    where
+        W: AsRef<WgpuContext>,
+    {
+        let buffers = self.tessellate()?;
+
+        match u16_indexes(&buffers) {
+            Some(indexes) => shape.update(wgpu, &buffers.vertices, &indexes),
+            None => shape.update(wgpu, &buffers.vertices, &buffers.indices),
+        }
+
+        Ok(())
+    }
+
+    // Queries include the path in progress with its open subpath treated as unclosed.
+    pub fn contains<P>(&self, point: P) -> bool
+    where
+        P: Into<Vec2>,
+    {
+        let point = convert_point(point);
+        let current_path = self.current_path();
+        let mut paths = self.query_paths(&current_path);
+        paths.any(|(path, style)| path_contains(path, style, point))
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        let current_path = self.current_path();
+
+        if self.paths.is_empty() && current_path.is_none() {
+            return Bounds::default();
+        }
 
-        let mut buffers = VertexBuffers::<ShapeVertex, u16>::new();
-        let mut buffers_builder = BuffersBuilder::new(&mut buffers, convert_vertex);
+        let (min, max) = self.query_paths(&current_path).fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(min, max), (path, style)| {
+                let path_bounds = bounding_box(path.iter());
+                let padding = if style.stroke {
+                    style.stroke_width * 0.5
+                } else {
+                    0.0
+                };
 
-        FillTessellator::new()
-            .tessellate_path(&path, &Default::default(), &mut buffers_builder)
-            .unwrap();
+                (
+                    min.min(Vec2::from(path_bounds.min.to_array()) - padding),
+                    max.max(Vec2::from(path_bounds.max.to_array()) + padding),
+                )
+            },
+        );
 
-        Shape::new(wgpu, &buffers.vertices, &buffers.indices)
+        Bounds::from_min_max(min, max)
     }
 
-    fn attributes(&self) -> [f32; 4] {
+    pub fn raycast<O, D>(&self, origin: O, direction: D) -> Option<RayHit>
+    where
+        O: Into<Vec2>,
+        D: Into<Vec2>,
+    {
+        let origin = origin.into();
+        let direction = direction.into().try_normalize()?;
+
+        if self.contains(origin) {
+            return Some(RayHit {
+                point: origin,
+                normal: -direction,
+                distance: 0.0,
+            });
+        }
+
+        let ray = Ray {
+            origin: convert_point(origin),
+            direction: convert_vector(direction),
+        };
+
+        let current_path = self.current_path();
+
+        self.query_paths(&current_path)
+            .filter_map(|(path, style)| {
+                let hit = raycast_path(&ray, path.iter(), style.tolerance)?;
+                let normal = Vec2::from(hit.normal.to_array()).normalize_or_zero();
+                let normal = if normal.dot(direction) > 0.0 { -normal } else { normal };
+                let mut distance = Vec2::from(hit.position.to_array()).distance(origin);
+
+                if style.stroke {
+                    let cos = normal.dot(direction).abs().max(f32::EPSILON);
+                    distance = (distance - style.stroke_width * 0.5 / cos).max(0.0);
+                }
+
+                Some(RayHit {
+                    point: origin + direction * distance,
+                    normal,
+                    distance,
+                })
+            })
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+
+    fn tessellate(&mut self) -> GameResult<VertexBuffers<ShapeVertex, u32>> {
+        self.finish_path();
+
+        let mut buffers = VertexBuffers::<ShapeVertex, u32>::new();
+        let mut uv_ranges = Vec::new();
+        let mut fill_tessellator = FillTessellator::new();
+        let mut stroke_tessellator = StrokeTessellator::new();
+
+        for (path, style) in mem::take(&mut self.paths) {
+            if style.fill {
+                let mut fill_buffers = VertexBuffers::new();
+
+                fill_tessellator
+                    .tessellate_path(
+                        &path,
+                        &style.fill_options(),
+                        &mut BuffersBuilder::new(&mut fill_buffers, convert_fill_vertex),
+                    )
+                    .context("Failed to tessellate shape fill")?;
+
+                let range = append_buffers(
+                    &mut buffers,
+                    fill_buffers,
+                    style.fill_gradient.as_ref(),
+                    style.tolerance,
+                );
+
+                uv_ranges.push((range, style.uv_bounds));
+            }
+
+            if style.stroke {
+                let path = if style.dashes.is_empty() {
+                    path
+                } else {
+                    dash_path(&path, &style.dashes, style.dash_offset, style.tolerance)
+                };
+
+                let stroke_color = style.stroke_color;
+                let mut stroke_buffers = VertexBuffers::new();
+
+                stroke_tessellator
+                    .tessellate_path(
+                        &path,
+                        &style.stroke_options(),
+                        &mut BuffersBuilder::new(&mut stroke_buffers, |mut vertex: StrokeVertex| {
+                            convert_vertex(
+                                vertex.position(),
+                                vertex.interpolated_attributes(),
+                                stroke_color,
+                            )
+                        }),
+                    )
+                    .context("Failed to tessellate shape stroke")?;
+
+                let range = append_buffers(
+                    &mut buffers,
+                    stroke_buffers,
+                    style.stroke_gradient.as_ref(),
+                    style.tolerance,
+                );
+
+                uv_ranges.push((range, style.uv_bounds));
+            }
+        }
+
+        let shape_bounds = vertex_bounds(&buffers.vertices);
+
+        for (range, uv_bounds) in uv_ranges {
+            map_uvs(
+                &mut buffers.vertices[range],
+                uv_bounds.unwrap_or(shape_bounds),
+            );
+        }
+
+        Ok(buffers)
+    }
+
+    fn attributes(&self) -> [f32; ATTRIBUTE_COUNT] {
         self.active_color.as_array()
     }
+
+    fn add_polygon(&mut self, points: &[Vec2], closed: bool) -> &mut Self {
+        let points = points
+            .iter()
+            .map(|point| convert_point(*point))
+            .collect::<Vec<_>>();
+
+        self.path_builder
+            .add_polygon(Polygon { points: &points, closed }, &self.attributes());
+
+        self
+    }
+
+    fn current_path(&self) -> Option<Path> {
+        let mut path_builder = self.path_builder.clone();
+
+        if self.current_position.is_some() {
+            path_builder.end(false);
+        }
+
+        let path = path_builder.build();
+        (!path.as_slice().is_empty()).then_some(path)
+    }
+
+    fn query_paths<'a>(
+        &'a self,
+        current_path: &'a Option<Path>,
+    ) -> impl Iterator<Item = (&'a Path, &'a PathStyle)> {
+        self.paths
+            .iter()
+            .map(|(path, style)| (path, style))
+            .chain(current_path.iter().map(|path| (path, &self.active_style)))
+    }
+
+    fn end_subpath(&mut self, close: bool) -> &mut Self {
+        if self.current_position.take().is_some() {
+            self.path_builder.end(close);
+        }
+
+        if self.pending_style.is_some() {
+            self.finish_path();
+        }
+
+        self
+    }
+
+    // Style changes made while a subpath is open apply to the next path.
+    fn update_style<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut PathStyle),
+    {
+        let mut style = self
+            .pending_style
+            .take()
+            .unwrap_or_else(|| self.active_style.clone());
+
+        f(&mut style);
+
+        if self.current_position.is_some() {
+            self.pending_style = Some(style);
+        } else {
+            self.finish_path();
+            self.active_style = style;
+        }
+
+        self
+    }
+
+    fn finish_path(&mut self) {
+        if self.current_position.take().is_some() {
+            self.path_builder.end(false);
+        }
+
+        let path_builder = mem::replace(&mut self.path_builder, new_path_builder());
+        let path = path_builder.build();
+
+        if !path.as_slice().is_empty() {
+            self.paths.push((path, self.active_style.clone()));
+        }
+
+        if let Some(style) = self.pending_style.take() {
+            self.active_style = style;
+        }
+    }
 }
 
 impl Default for ShapeBuilder {
@@ -190,13 +897,17 @@ impl Default for ShapeBuilder {
     fn default() -> Self {
         Self {
             path_builder: new_path_builder(),
+            paths: Vec::new(),
+            current_position: None,
             active_color: Color::WHITE,
+            active_style: PathStyle::default(),
+            pending_style: None,
         }
     }
 }
 
 fn new_path_builder() -> BuilderWithAttributes {
-    Path::builder_with_attributes(4)
+    Path::builder_with_attributes(ATTRIBUTE_COUNT)
 }
 
 fn convert_point<P>(point: P) -> Point
@@ -215,13 +926,247 @@ where
     Vector::new(vec.x, vec.y)
 }
 
-fn convert_vertex(mut vertex: FillVertex) -> ShapeVertex {
-    let attributes = vertex.interpolated_attributes();
-    let color = Color::rgba(attributes[0], attributes[1], attributes[2], attributes[3]);
+fn convert_fill_vertex(mut vertex: FillVertex) -> ShapeVertex {
+    convert_vertex(vertex.position(), vertex.interpolated_attributes(), None)
+}
+
+fn convert_vertex(position: Point, attributes: &[f32], color: Option<Color>) -> ShapeVertex {
+    let color = color.unwrap_or_else(|| {
+        Color::rgba(attributes[0], attributes[1], attributes[2], attributes[3])
+    });
 
     ShapeVertex {
-        position: vertex.position().to_array().into(),
+        position: position.to_array().into(),
         linear_color: color.to_linear_vec4(),
         ..Default::default()
     }
 }
+
+fn append_buffers(
+    buffers: &mut VertexBuffers<ShapeVertex, u32>,
+    part: VertexBuffers<ShapeVertex, u32>,
+    gradient: Option<&Gradient>,
+    tolerance: f32,
+) -> Range<usize> {
+    let (vertexes, indexes) = match gradient {
+        Some(gradient) => apply_gradient(&part.vertices, &part.indices, gradient, tolerance),
+        None => (part.vertices, part.indices),
+    };
+
+    let start = buffers.vertices.len();
+    let offset = start as u32;
+    buffers.vertices.extend(vertexes);
+    buffers
+        .indices
+        .extend(indexes.into_iter().map(|index| index + offset));
+
+    start..buffers.vertices.len()
+}
+
+fn path_contains(path: &Path, style: &PathStyle, point: Point) -> bool {
+    if style.fill && hit_test_path(&point, path.iter(), style.fill_rule.into(), style.tolerance) {
+        return true;
+    }
+
+    if !style.stroke {
+        return false;
+    }
+
+    let half_width = style.stroke_width * 0.5;
+
+    path.iter()
+        .flattened(style.tolerance)
+        .any(|event| match event {
+            PathEvent::Line { from, to }
+            | PathEvent::End {
+                last: from,
+                first: to,
+                close: true,
+            } => LineSegment { from, to }.distance_to_point(point) <= half_width,
+            _ => false,
+        })
+}
+
+fn u16_indexes(buffers: &VertexBuffers<ShapeVertex, u32>) -> Option<Vec<u16>> {
+    if buffers.vertices.len() > u16::MAX as usize + 1 {
+        return None;
+    }
+
+    Some(buffers.indices.iter().map(|&index| index as u16).collect())
+}
+
+fn map_uvs(vertexes: &mut [ShapeVertex], uv_bounds: Bounds) {
+    let size = uv_bounds.size().max(Vec2::splat(f32::EPSILON));
+
+    for vertex in vertexes.iter_mut() {
+        vertex.uv = (vertex.position - uv_bounds.top_left()) / size;
+    }
+}
+
+fn dash_path(path: &Path, dashes: &[f32], dash_offset: f32, tolerance: f32) -> Path {
+    let mut pattern = dashes.iter().map(|dash| dash.max(0.0)).collect::<Vec<_>>();
+
+    if !pattern.len().is_multiple_of(2) {
+        pattern.extend_from_slice(&pattern.clone());
+    }
+
+    if pattern.iter().sum::<f32>() <= 0.0 {
+        return path.clone();
+    }
+
+    let mut dasher = Dasher::new(&pattern, dash_offset);
+
+    for event in path.iter_with_attributes() {
+        match event {
+            Event::Begin { .. } => dasher.begin(),
+            Event::Line { from, to } => dasher.line(from.0, from.1, to.0, to.1),
+            Event::Quadratic { from, ctrl, to } => {
+                let segment = QuadraticBezierSegment {
+                    from: from.0,
+                    ctrl,
+                    to: to.0,
+                };
+
+                segment.for_each_flattened_with_t(tolerance, &mut |line, t| {
+                    dasher.line(
+                        line.from,
+                        &lerp_attributes(from.1, to.1, t.start),
+                        line.to,
+                        &lerp_attributes(from.1, to.1, t.end),
+                    );
+                });
+            }
+            Event::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => {
+                let segment = CubicBezierSegment {
+                    from: from.0,
+                    ctrl1,
+                    ctrl2,
+                    to: to.0,
+                };
+
+                segment.for_each_flattened_with_t(tolerance, &mut |line, t| {
+                    dasher.line(
+                        line.from,
+                        &lerp_attributes(from.1, to.1, t.start),
+                        line.to,
+                        &lerp_attributes(from.1, to.1, t.end),
+                    );
+                });
+            }
+            Event::End { last, first, close } => {
+                if close {
+                    dasher.line(last.0, last.1, first.0, first.1);
+                }
+
+                dasher.end();
+            }
+        }
+    }
+
+    dasher.path_builder.build()
+}
+
+fn lerp_attributes(from: &[f32], to: &[f32], t: f32) -> [f32; ATTRIBUTE_COUNT] {
+    let mut attributes = [0.0; ATTRIBUTE_COUNT];
+
+    for (attribute, (from, to)) in attributes.iter_mut().zip(from.iter().zip(to)) {
+        *attribute = from + (to - from) * t;
+    }
+
+    attributes
+}
+
+struct Dasher<'a> {
+    pattern: &'a [f32],
+    dash_offset: f32,
+    path_builder: PathBuilder,
+    index: usize,
+    remaining: f32,
+    drawing: bool,
+}
+
+impl<'a> Dasher<'a> {
+    fn new(pattern: &'a [f32], dash_offset: f32) -> Self {
+        Self {
+            pattern,
+            dash_offset,
+            path_builder: new_path_builder(),
+            index: 0,
+            remaining: 0.0,
+            drawing: false,
+        }
+    }
+
+    fn begin(&mut self) {
+        let total = self.pattern.iter().sum::<f32>();
+        let mut offset = self.dash_offset.rem_euclid(total);
+
+        self.index = 0;
+
+        while offset >= self.pattern[self.index] {
+            offset -= self.pattern[self.index];
+            self.index = (self.index + 1) % self.pattern.len();
+        }
+
+        self.remaining = self.pattern[self.index] - offset;
+        self.drawing = false;
+    }
+
+    fn line(&mut self, from: Point, from_attributes: &[f32], to: Point, to_attributes: &[f32]) {
+        let length = (to - from).length();
+
+        if length <= 0.0 {
+            return;
+        }
+
+        let mut distance = 0.0;
+
+        while distance < length {
+            let dash = self.index.is_multiple_of(2);
+            let start = distance;
+            let step = self.remaining.min(length - distance);
+
+            distance += step;
+            self.remaining -= step;
+
+            if dash {
+                if !self.drawing {
+                    let t = start / length;
+                    self.path_builder.begin(
+                        from.lerp(to, t),
+                        &lerp_attributes(from_attributes, to_attributes, t),
+                    );
+                    self.drawing = true;
+                }
+
+                let t = distance / length;
+                self.path_builder.line_to(
+                    from.lerp(to, t),
+                    &lerp_attributes(from_attributes, to_attributes, t),
+                );
+            }
+
+            if self.remaining <= 0.0 {
+                if dash {
+                    self.end();
+                }
+
+                self.index = (self.index + 1) % self.pattern.len();
+                self.remaining = self.pattern[self.index];
+            }
+        }
+    }
+
+    fn end(&mut self) {
+        if self.drawing {
+            self.path_builder.end(false);
+            self.drawing = false;
+        }
+    }
+}
+
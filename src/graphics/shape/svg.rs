@@ -0,0 +1,365 @@
+use crate::game::GameResult;
+use crate::graphics::shape::{
+    FillRule, Gradient, GradientStop, LineCap, LineJoin, Shape, ShapeBuilder,
+};
+use crate::graphics::{Color, WgpuContext};
+use anyhow::Context;
+use glam::{Affine2, Vec2};
+use lyon::tessellation::FillOptions;
+use std::path::Path;
+use usvg::tiny_skia_path::PathSegment;
+
+#[derive(Clone, Copy, Debug)]
+pub struct SvgOptions {
+    pub tolerance: f32,
+    pub merge_paths: bool,
+}
+
+impl SvgOptions {
+    pub fn tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn merge_paths(mut self, merge_paths: bool) -> Self {
+        self.merge_paths = merge_paths;
+        self
+    }
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: FillOptions::DEFAULT_TOLERANCE,
+            merge_paths: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SvgShape {
+    pub id: String,
+    pub shape: Shape,
+}
+
+#[derive(Clone, Debug)]
+pub struct Svg {
+    pub size: Vec2,
+    pub shapes: Vec<SvgShape>,
+}
+
+impl Svg {
+    pub fn from_file<W, P>(wgpu: W, path: P) -> GameResult<Self>
+    where
+        W: AsRef<WgpuContext>,
+        P: AsRef<Path>,
+    {
+        Self::from_file_with_options(wgpu, path, &SvgOptions::default())
+    }
+
+    pub fn from_file_with_options<W, P>(
+        wgpu: W,
+        path: P,
+        options: &SvgOptions,
+    ) -> GameResult<Self>
+    where
+        W: AsRef<WgpuContext>,
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read SVG file: '{}'", path.display()))?;
+
+        Self::from_bytes_with_options(wgpu, &bytes, options)
+            .with_context(|| format!("Failed to load SVG file: '{}'", path.display()))
+    }
+
+    pub fn from_bytes<W>(wgpu: W, bytes: &[u8]) -> GameResult<Self>
+    where
+        W: AsRef<WgpuContext>,
+    {
+        Self::from_bytes_with_options(wgpu, bytes, &SvgOptions::default())
+    }
+
+    pub fn from_bytes_with_options<W>(
+        wgpu: W,
+        bytes: &[u8],
+        options: &SvgOptions,
+    ) -> GameResult<Self>
+    where
+        W: AsRef<WgpuContext>,
+    {
+        let wgpu = wgpu.as_ref();
+        let tree = usvg::Tree::from_data(bytes, &usvg::Options::default())
+            .context("Failed to parse SVG")?;
+
+        let mut paths = Vec::new();
+        collect_paths(tree.root(), 1.0, &mut paths);
+
+        let shapes = if options.merge_paths {
+            let mut builder = ShapeBuilder::default();
+
+            for (path, opacity) in paths {
+                add_path(&mut builder, path, opacity, options);
+            }
+
+            vec![SvgShape {
+                id: tree.root().id().to_string(),
+                shape: builder.build(wgpu)?,
+            }]
+        } else {
+            paths
+                .into_iter()
+                .map(|(path, opacity)| {
+                    let mut builder = ShapeBuilder::default();
+                    add_path(&mut builder, path, opacity, options);
+
+                    Ok(SvgShape {
+                        id: path.id().to_string(),
+                        shape: builder.build(wgpu)?,
+                    })
+                })
+                .collect::<GameResult<Vec<_>>>()?
+        };
+
+        let size = tree.size();
+
+        Ok(Self {
+            size: Vec2::new(size.width(), size.height()),
+            shapes,
+        })
+    }
+
+    pub fn shape(&self, id: &str) -> Option<&Shape> {
+        self.shapes
+            .iter()
+            .find(|shape| shape.id == id)
+            .map(|shape| &shape.shape)
+    }
+}
+
+fn collect_paths<'a>(
+    group: &'a usvg::Group,
+    opacity: f32,
+    paths: &mut Vec<(&'a usvg::Path, f32)>,
+) {
+    let opacity = opacity * group.opacity().get();
+
+    for node in group.children() {
+        match node {
+            usvg::Node::Group(group) => collect_paths(group, opacity, paths),
+            usvg::Node::Path(path) if path.is_visible() => paths.push((path, opacity)),
+            _ => (),
+        }
+    }
+}
+
+fn add_path(builder: &mut ShapeBuilder, path: &usvg::Path, opacity: f32, options: &SvgOptions) {
+    let transform = convert_transform(path.abs_transform());
+
+    match path.paint_order() {
+        usvg::PaintOrder::FillAndStroke => {
+            add_path_part(builder, path, path.fill(), path.stroke(), transform, opacity, options);
+        }
+        usvg::PaintOrder::StrokeAndFill => {
+            add_path_part(builder, path, None, path.stroke(), transform, opacity, options);
+            add_path_part(builder, path, path.fill(), None, transform, opacity, options);
+        }
+    }
+}
+
+fn add_path_part(
+    builder: &mut ShapeBuilder,
+    path: &usvg::Path,
+    fill: Option<&usvg::Fill>,
+    stroke: Option<&usvg::Stroke>,
+    transform: Affine2,
+    opacity: f32,
+    options: &SvgOptions,
+) {
+    let scale = transform.matrix2.determinant().abs().sqrt();
+
+    let fill = fill.and_then(|fill| {
+        let opacity = opacity * fill.opacity().get();
+        Some((fill, convert_paint(fill.paint(), transform, opacity)?))
+    });
+
+    let stroke = stroke.and_then(|stroke| {
+        let opacity = opacity * stroke.opacity().get();
+        Some((stroke, convert_paint(stroke.paint(), transform, opacity)?))
+    });
+
+    builder.clear_gradients().tolerance(options.tolerance);
+
+    match (&fill, &stroke) {
+        (Some(_), Some((stroke, _))) => builder.fill_and_stroke(stroke.width().get() * scale),
+        (Some(_), None) => builder.fill(),
+        (None, Some((stroke, _))) => builder.stroke(stroke.width().get() * scale),
+        (None, None) => return,
+    };
+
+    if let Some((fill, paint)) = fill {
+        match paint {
+            Paint::Color(color) => builder.color(color),
+            Paint::Gradient(gradient) => builder.color(Color::WHITE).fill_gradient(gradient),
+        };
+
+        builder.fill_rule(match fill.rule() {
+            usvg::FillRule::NonZero => FillRule::NonZero,
+            usvg::FillRule::EvenOdd => FillRule::EvenOdd,
+        });
+    }
+
+    if let Some((stroke, paint)) = stroke {
+        match paint {
+            Paint::Color(color) => builder.stroke_color(color),
+            Paint::Gradient(gradient) => {
+                builder.stroke_color(Color::WHITE).stroke_gradient(gradient)
+            }
+        };
+
+        let dashes = stroke
+            .dasharray()
+            .unwrap_or_default()
+            .iter()
+            .map(|dash| dash * scale)
+            .collect::<Vec<_>>();
+
+        builder
+            .line_cap(match stroke.linecap() {
+                usvg::LineCap::Butt => LineCap::Butt,
+                usvg::LineCap::Square => LineCap::Square,
+                usvg::LineCap::Round => LineCap::Round,
+            })
+            .line_join(match stroke.linejoin() {
+                usvg::LineJoin::Miter => LineJoin::Miter,
+                usvg::LineJoin::MiterClip => LineJoin::MiterClip,
+                usvg::LineJoin::Round => LineJoin::Round,
+                usvg::LineJoin::Bevel => LineJoin::Bevel,
+            })
+            .miter_limit(stroke.miterlimit().get())
+            .dashes(&dashes)
+            .dash_offset(stroke.dashoffset() * scale);
+    }
+
+    add_segments(builder, path, transform);
+}
+
+fn add_segments(builder: &mut ShapeBuilder, path: &usvg::Path, transform: Affine2) {
+    let point = |point: usvg::tiny_skia_path::Point| {
+        transform.transform_point2(Vec2::new(point.x, point.y))
+    };
+
+    let mut start = Vec2::ZERO;
+    let mut open = false;
+
+    for segment in path.data().segments() {
+        if !open && !matches!(segment, PathSegment::MoveTo(_) | PathSegment::Close) {
+            builder.begin(start);
+            open = true;
+        }
+
+        match segment {
+            PathSegment::MoveTo(to) => {
+                if open {
+                    builder.end_open();
+                }
+
+                start = point(to);
+                builder.begin(start);
+                open = true;
+            }
+            PathSegment::LineTo(to) => {
+                builder.line_to(point(to));
+            }
+            PathSegment::QuadTo(ctrl, to) => {
+                builder.quadratic_bezier_to(point(ctrl), point(to));
+            }
+            PathSegment::CubicTo(ctrl1, ctrl2, to) => {
+                builder.cubic_bezier_to(point(ctrl1), point(ctrl2), point(to));
+            }
+            PathSegment::Close => {
+                if open {
+                    builder.end();
+                    open = false;
+                }
+            }
+        }
+    }
+
+    if open {
+        builder.end_open();
+    }
+}
+
+enum Paint {
+    Color(Color),
+    Gradient(Gradient),
+}
+
+fn convert_paint(paint: &usvg::Paint, transform: Affine2, opacity: f32) -> Option<Paint> {
+    match paint {
+        usvg::Paint::Color(color) => Some(Paint::Color(convert_color(*color, opacity))),
+        usvg::Paint::LinearGradient(gradient) => {
+            let transform = transform * convert_transform(gradient.transform());
+            let start = Vec2::new(gradient.x1(), gradient.y1());
+            let direction = Vec2::new(gradient.x2(), gradient.y2()) - start;
+
+            // Gradient offsets are linear in both spaces, so project the direction
+            // through the inverse transform to keep the iso-lines in the right place.
+            let axis = transform.matrix2.inverse().transpose() * direction
+                / direction.length_squared().max(f32::EPSILON);
+            let axis = axis / axis.length_squared().max(f32::EPSILON);
+            let start = transform.transform_point2(start);
+
+            Some(Paint::Gradient(Gradient::linear(
+                start,
+                start + axis,
+                convert_stops(gradient.stops(), opacity),
+            )))
+        }
+        usvg::Paint::RadialGradient(gradient) => {
+            let transform = transform * convert_transform(gradient.transform());
+            let scale = transform.matrix2.determinant().abs().sqrt();
+
+            Some(Paint::Gradient(Gradient::radial(
+                transform.transform_point2(Vec2::new(gradient.cx(), gradient.cy())),
+                gradient.r().get() * scale,
+                convert_stops(gradient.stops(), opacity),
+            )))
+        }
+        usvg::Paint::Pattern(_) => None,
+    }
+}
+
+fn convert_stops(stops: &[usvg::Stop], opacity: f32) -> Vec<GradientStop> {
+    stops
+        .iter()
+        .map(|stop| {
+            GradientStop::new(
+                stop.offset().get(),
+                convert_color(stop.color(), opacity * stop.opacity().get()),
+            )
+        })
+        .collect()
+}
+
+fn convert_color(color: usvg::Color, opacity: f32) -> Color {
+    Color::rgba(
+        f32::from(color.red) / 255.0,
+        f32::from(color.green) / 255.0,
+        f32::from(color.blue) / 255.0,
+        opacity,
+    )
+}
+
+fn convert_transform(transform: usvg::Transform) -> Affine2 {
+    Affine2::from_cols_array(&[
+        transform.sx,
+        transform.ky,
+        transform.kx,
+        transform.sy,
+        transform.tx,
+        transform.ty,
+    ])
+}
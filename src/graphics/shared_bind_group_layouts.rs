@@ -5,6 +5,8 @@ struct SharedBindGroupLayoutsData {
     projection: wgpu::BindGroupLayout,
     texture: wgpu::BindGroupLayout,
     sampler: wgpu::BindGroupLayout,
+    uniform: wgpu::BindGroupLayout,
+    texture_3d: wgpu::BindGroupLayout,
 }
 
 impl SharedBindGroupLayoutsData {
@@ -47,10 +49,40 @@ impl SharedBindGroupLayoutsData {
             }],
         });
 
+        let uniform = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("uniform_bind_group_layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let texture_3d = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("texture_3d_bind_group_layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D3,
+                    multisampled: false,
+                },
+                count: None,
+            }],
+        });
+
         Self {
             projection,
             texture,
             sampler,
+            uniform,
+            texture_3d,
         }
     }
 }
@@ -77,4 +109,14 @@ impl SharedBindGroupLayouts {
     pub fn sampler(&self) -> &wgpu::BindGroupLayout {
         &self.0.sampler
     }
+
+    #[inline]
+    pub fn uniform(&self) -> &wgpu::BindGroupLayout {
+        &self.0.uniform
+    }
+
+    #[inline]
+    pub fn texture_3d(&self) -> &wgpu::BindGroupLayout {
+        &self.0.texture_3d
+    }
 }
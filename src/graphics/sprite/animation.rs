@@ -0,0 +1,256 @@
+use crate::graphics::sprite::{Sprite, SpriteSheet};
+use std::ops::Range;
+
+const MIN_FRAME_DURATION: f32 = 0.001;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum AnimationMode {
+    #[default]
+    Loop,
+    PingPong,
+    Once,
+}
+
+#[derive(Clone, Debug)]
+pub struct AnimationFrame {
+    pub index: usize,
+    pub duration: f32,
+    pub event: Option<String>,
+}
+
+impl AnimationFrame {
+    pub fn new(index: usize, duration: f32) -> Self {
+        Self {
+            index,
+            duration,
+            event: None,
+        }
+    }
+
+    pub fn event<E>(mut self, event: E) -> Self
+    where
+        E: Into<String>,
+    {
+        self.event = Some(event.into());
+        self
+    }
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct Animation {
+    pub frames: Vec<AnimationFrame>,
+    pub mode: AnimationMode,
+    pub flip_x: bool,
+    pub flip_y: bool,
+}
+
+impl Animation {
+    pub fn new<F>(frames: F) -> Self
+    where
+        F: IntoIterator<Item = AnimationFrame>,
+    {
+        Self {
+            frames: frames.into_iter().collect(),
+            mode: AnimationMode::Loop,
+            flip_x: false,
+            flip_y: false,
+        }
+    }
+
+    pub fn from_range(indexes: Range<usize>, frame_duration: f32) -> Self {
+        Self::new(indexes.map(|index| AnimationFrame::new(index, frame_duration)))
+    }
+
+    pub fn mode(mut self, mode: AnimationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn flip_x(mut self, flip_x: bool) -> Self {
+        self.flip_x = flip_x;
+        self
+    }
+
+    pub fn flip_y(mut self, flip_y: bool) -> Self {
+        self.flip_y = flip_y;
+        self
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AnimationPlayer {
+    animation: Animation,
+    speed: f32,
+    current_frame: usize,
+    elapsed: f32,
+    reversed: bool,
+    playing: bool,
+    started: bool,
+    finished: bool,
+    events: Vec<String>,
+}
+
+impl AnimationPlayer {
+    pub fn new(animation: Animation) -> Self {
+        Self {
+            animation,
+            speed: 1.0,
+            current_frame: 0,
+            elapsed: 0.0,
+            reversed: false,
+            playing: true,
+            started: false,
+            finished: false,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn animation(&self) -> &Animation {
+        &self.animation
+    }
+
+    pub fn set_animation(&mut self, animation: Animation) {
+        self.animation = animation;
+        self.reset();
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    pub fn reset(&mut self) {
+        self.current_frame = 0;
+        self.elapsed = 0.0;
+        self.reversed = false;
+        self.playing = true;
+        self.started = false;
+        self.finished = false;
+        self.events.clear();
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn update(&mut self, delta: f32) {
+        self.events.clear();
+
+        if !self.playing || self.animation.frames.is_empty() {
+            return;
+        }
+
+        if !self.started {
+            self.started = true;
+            self.push_current_event();
+        }
+
+        self.elapsed += delta * self.speed;
+
+        loop {
+            let duration = self.animation.frames[self.current_frame]
+                .duration
+                .max(MIN_FRAME_DURATION);
+
+            if self.elapsed < duration {
+                break;
+            }
+
+            if !self.advance() {
+                self.elapsed = 0.0;
+                self.playing = false;
+                self.finished = true;
+                break;
+            }
+
+            self.elapsed -= duration;
+            self.push_current_event();
+        }
+    }
+
+    fn advance(&mut self) -> bool {
+        let last_frame = self.animation.frames.len() - 1;
+
+        match self.animation.mode {
+            AnimationMode::Loop => {
+                self.current_frame = if self.current_frame < last_frame {
+                    self.current_frame + 1
+                } else {
+                    0
+                };
+            }
+            AnimationMode::PingPong => {
+                if last_frame == 0 {
+                    return true;
+                }
+
+                if self.reversed && self.current_frame == 0 {
+                    self.reversed = false;
+                } else if !self.reversed && self.current_frame == last_frame {
+                    self.reversed = true;
+                }
+
+                if self.reversed {
+                    self.current_frame -= 1;
+                } else {
+                    self.current_frame += 1;
+                }
+            }
+            AnimationMode::Once => {
+                if self.current_frame == last_frame {
+                    return false;
+                }
+
+                self.current_frame += 1;
+            }
+        }
+
+        true
+    }
+
+    fn push_current_event(&mut self) {
+        if let Some(event) = self.animation.frames[self.current_frame].event.as_ref() {
+            self.events.push(event.clone());
+        }
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = &str> {
+        self.events.iter().map(String::as_str)
+    }
+
+    pub fn current_frame(&self) -> Option<&AnimationFrame> {
+        self.animation.frames.get(self.current_frame)
+    }
+
+    pub fn frame_index(&self) -> usize {
+        self.current_frame().map_or(0, |frame| frame.index)
+    }
+
+    pub fn sprite<'a>(&self, sprite_sheet: &'a SpriteSheet) -> Option<Sprite<'a>> {
+        let sprite = sprite_sheet
+            .get(self.current_frame()?.index)?
+            .flip_x(self.animation.flip_x)
+            .flip_y(self.animation.flip_y);
+
+        Some(sprite)
+    }
+}
@@ -0,0 +1,533 @@
+use crate::game::GameResult;
+use crate::graphics::sprite::{
+    AsepriteDirection, AsepriteOptions, AsepriteSlice, AsepriteSliceKey, AsepriteTag,
+};
+use crate::graphics::Bounds;
+use anyhow::{anyhow, bail, ensure};
+use flate2::read::ZlibDecoder;
+use glam::{IVec2, UVec2, Vec2};
+use image::RgbaImage;
+use std::io::Read;
+
+const HEADER_MAGIC: u16 = 0xA5E0;
+const FRAME_MAGIC: u16 = 0xF1FA;
+const HEADER_SIZE: usize = 128;
+
+const CHUNK_OLD_PALETTE: u16 = 0x0004;
+const CHUNK_LAYER: u16 = 0x2004;
+const CHUNK_CEL: u16 = 0x2005;
+const CHUNK_TAGS: u16 = 0x2018;
+const CHUNK_PALETTE: u16 = 0x2019;
+const CHUNK_SLICE: u16 = 0x2022;
+
+const LAYER_FLAG_VISIBLE: u16 = 1;
+const LAYER_TYPE_NORMAL: u16 = 0;
+const HEADER_FLAG_LAYER_OPACITY: u32 = 1;
+
+const CEL_TYPE_RAW: u16 = 0;
+const CEL_TYPE_LINKED: u16 = 1;
+const CEL_TYPE_COMPRESSED: u16 = 2;
+
+const SLICE_FLAG_NINE_PATCH: u32 = 1;
+const SLICE_FLAG_PIVOT: u32 = 2;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ColorDepth {
+    Rgba,
+    Grayscale,
+    Indexed,
+}
+
+impl ColorDepth {
+    fn bytes_per_pixel(self) -> usize {
+        match self {
+            Self::Rgba => 4,
+            Self::Grayscale => 2,
+            Self::Indexed => 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Layer {
+    visible: bool,
+    drawable: bool,
+    opacity: u8,
+}
+
+#[derive(Clone, Debug)]
+struct Cel {
+    layer: usize,
+    position: IVec2,
+    opacity: u8,
+    z_index: i16,
+    image: RgbaImage,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct AseFile {
+    pub size: UVec2,
+    pub frames: Vec<RgbaImage>,
+    pub frame_durations: Vec<f32>,
+    pub tags: Vec<AsepriteTag>,
+    pub slices: Vec<AsepriteSlice>,
+}
+
+impl AseFile {
+    pub fn parse(bytes: &[u8], options: &AsepriteOptions) -> GameResult<Self> {
+        let mut header = Reader::new(bytes);
+        header.skip(4)?;
+        ensure!(header.u16()? == HEADER_MAGIC, "Invalid Aseprite header");
+
+        let frame_count = header.u16()? as usize;
+        let size = UVec2::new(header.u16()? as u32, header.u16()? as u32);
+
+        let color_depth = match header.u16()? {
+            32 => ColorDepth::Rgba,
+            16 => ColorDepth::Grayscale,
+            8 => ColorDepth::Indexed,
+            depth => bail!("Unsupported Aseprite color depth: {depth}"),
+        };
+
+        let flags = header.u32()?;
+        header.skip(10)?;
+        let transparent_index = header.u8()?;
+
+        let mut reader = Reader::new(bytes);
+        reader.skip(HEADER_SIZE)?;
+
+        let mut layers = Vec::<Layer>::new();
+        let mut group_visibility = Vec::<bool>::new();
+        let mut palette = Vec::<[u8; 4]>::new();
+        let mut frame_cels = Vec::<Vec<Cel>>::with_capacity(frame_count);
+        let mut frame_durations = Vec::with_capacity(frame_count);
+        let mut tags = Vec::new();
+        let mut slices = Vec::new();
+
+        for _ in 0..frame_count {
+            let frame_start = reader.offset;
+            let frame_size = reader.u32()? as usize;
+            ensure!(reader.u16()? == FRAME_MAGIC, "Invalid Aseprite frame header");
+
+            let old_chunk_count = reader.u16()? as usize;
+            let duration = reader.u16()?;
+            reader.skip(2)?;
+            let new_chunk_count = reader.u32()? as usize;
+
+            let chunk_count = if new_chunk_count == 0 {
+                old_chunk_count
+            } else {
+                new_chunk_count
+            };
+
+            frame_durations.push(duration as f32 / 1000.0);
+
+            let mut cels = Vec::<Cel>::new();
+
+            for _ in 0..chunk_count {
+                let chunk_start = reader.offset;
+                let chunk_size = reader.u32()? as usize;
+                let chunk_type = reader.u16()?;
+                let chunk_end = chunk_start + chunk_size;
+                let mut chunk = Reader::new(reader.slice(chunk_start + 6, chunk_end)?);
+
+                match chunk_type {
+                    CHUNK_LAYER => {
+                        let layer_flags = chunk.u16()?;
+                        let layer_type = chunk.u16()?;
+                        let child_level = chunk.u16()? as usize;
+                        chunk.skip(6)?;
+                        let opacity = chunk.u8()?;
+                        chunk.skip(3)?;
+                        let name = chunk.string()?;
+
+                        let own_visible = options.include_hidden_layers
+                            || options
+                                .layer_visibility
+                                .get(&name)
+                                .copied()
+                                .unwrap_or(layer_flags & LAYER_FLAG_VISIBLE != 0);
+
+                        group_visibility.truncate(child_level);
+                        let parent_visible = group_visibility.last().copied().unwrap_or(true);
+                        let visible = own_visible && parent_visible;
+                        group_visibility.push(visible);
+
+                        layers.push(Layer {
+                            visible,
+                            drawable: layer_type == LAYER_TYPE_NORMAL,
+                            opacity: if flags & HEADER_FLAG_LAYER_OPACITY != 0 {
+                                opacity
+                            } else {
+                                255
+                            },
+                        });
+                    }
+                    CHUNK_CEL => {
+                        let layer = chunk.u16()? as usize;
+                        let position = IVec2::new(chunk.i16()? as i32, chunk.i16()? as i32);
+                        let opacity = chunk.u8()?;
+                        let cel_type = chunk.u16()?;
+                        let z_index = chunk.i16()?;
+                        chunk.skip(5)?;
+
+                        let image = match cel_type {
+                            CEL_TYPE_RAW | CEL_TYPE_COMPRESSED => {
+                                let width = chunk.u16()? as u32;
+                                let height = chunk.u16()? as u32;
+                                let pixel_count = (width * height) as usize;
+                                let data_size = pixel_count * color_depth.bytes_per_pixel();
+
+                                let data = if cel_type == CEL_TYPE_RAW {
+                                    chunk.bytes(data_size)?.to_vec()
+                                } else {
+                                    let mut data = Vec::new();
+                                    ZlibDecoder::new(chunk.remaining())
+                                        .take(data_size as u64)
+                                        .read_to_end(&mut data)
+                                        .map_err(|_| anyhow!("Invalid compressed Aseprite cel"))?;
+                                    data
+                                };
+
+                                ensure!(data.len() >= data_size, "Invalid Aseprite cel data");
+
+                                let pixels = data[..data_size]
+                                    .chunks_exact(color_depth.bytes_per_pixel())
+                                    .flat_map(|pixel| {
+                                        to_rgba(pixel, color_depth, &palette, transparent_index)
+                                    })
+                                    .collect::<Vec<_>>();
+
+                                RgbaImage::from_raw(width, height, pixels).unwrap()
+                            }
+                            CEL_TYPE_LINKED => {
+                                let linked_frame = chunk.u16()? as usize;
+
+                                let Some(linked_cel) = frame_cels
+                                    .get(linked_frame)
+                                    .and_then(|cels| cels.iter().find(|cel| cel.layer == layer))
+                                else {
+                                    continue;
+                                };
+
+                                linked_cel.image.clone()
+                            }
+                            _ => continue,
+                        };
+
+                        cels.push(Cel {
+                            layer,
+                            position,
+                            opacity,
+                            z_index,
+                            image,
+                        });
+                    }
+                    CHUNK_PALETTE => {
+                        let new_size = chunk.u32()? as usize;
+                        let first = chunk.u32()? as usize;
+                        let last = chunk.u32()? as usize;
+                        chunk.skip(8)?;
+
+                        palette.resize(new_size.max(palette.len()), [0; 4]);
+
+                        for i in first..=last {
+                            let entry_flags = chunk.u16()?;
+                            let color = [chunk.u8()?, chunk.u8()?, chunk.u8()?, chunk.u8()?];
+
+                            if entry_flags & 1 != 0 {
+                                chunk.string()?;
+                            }
+
+                            if let Some(entry) = palette.get_mut(i) {
+                                *entry = color;
+                            }
+                        }
+                    }
+                    CHUNK_OLD_PALETTE if palette.is_empty() => {
+                        let packet_count = chunk.u16()?;
+                        let mut index = 0;
+
+                        for _ in 0..packet_count {
+                            index += chunk.u8()? as usize;
+
+                            let color_count = match chunk.u8()? {
+                                0 => 256,
+                                count => count as usize,
+                            };
+
+                            palette.resize(palette.len().max(index + color_count), [0; 4]);
+
+                            for _ in 0..color_count {
+                                palette[index] = [chunk.u8()?, chunk.u8()?, chunk.u8()?, 255];
+                                index += 1;
+                            }
+                        }
+                    }
+                    CHUNK_TAGS => {
+                        let tag_count = chunk.u16()?;
+                        chunk.skip(8)?;
+
+                        for _ in 0..tag_count {
+                            let from = chunk.u16()? as usize;
+                            let to = chunk.u16()? as usize;
+
+                            let direction = match chunk.u8()? {
+                                1 => AsepriteDirection::Reverse,
+                                2 => AsepriteDirection::PingPong,
+                                3 => AsepriteDirection::PingPongReverse,
+                                _ => AsepriteDirection::Forward,
+                            };
+
+                            let repeat = chunk.u16()? as u32;
+                            chunk.skip(10)?;
+                            let name = chunk.string()?;
+
+                            tags.push(AsepriteTag {
+                                name,
+                                from,
+                                to,
+                                direction,
+                                repeat,
+                            });
+                        }
+                    }
+                    CHUNK_SLICE => {
+                        let key_count = chunk.u32()?;
+                        let slice_flags = chunk.u32()?;
+                        chunk.skip(4)?;
+                        let name = chunk.string()?;
+                        let mut keys = Vec::new();
+
+                        for _ in 0..key_count {
+                            let frame = chunk.u32()? as usize;
+                            let bounds = chunk.bounds()?;
+
+                            let center = if slice_flags & SLICE_FLAG_NINE_PATCH != 0 {
+                                Some(chunk.bounds()?)
+                            } else {
+                                None
+                            };
+
+                            let pivot = if slice_flags & SLICE_FLAG_PIVOT != 0 {
+                                Some(Vec2::new(chunk.i32()? as f32, chunk.i32()? as f32))
+                            } else {
+                                None
+                            };
+
+                            keys.push(AsepriteSliceKey {
+                                frame,
+                                bounds,
+                                center,
+                                pivot,
+                            });
+                        }
+
+                        slices.push(AsepriteSlice { name, keys });
+                    }
+                    _ => (),
+                }
+
+                reader.offset = chunk_end;
+            }
+
+            reader.offset = frame_start + frame_size;
+            frame_cels.push(cels);
+        }
+
+        let frames = frame_cels
+            .iter()
+            .map(|cels| compose_frame(size, &layers, cels))
+            .collect();
+
+        Ok(Self {
+            size,
+            frames,
+            frame_durations,
+            tags,
+            slices,
+        })
+    }
+}
+
+fn compose_frame(size: UVec2, layers: &[Layer], cels: &[Cel]) -> RgbaImage {
+    let mut frame = RgbaImage::new(size.x, size.y);
+
+    let mut cels = cels
+        .iter()
+        .filter(|cel| {
+            layers
+                .get(cel.layer)
+                .is_some_and(|layer| layer.visible && layer.drawable)
+        })
+        .collect::<Vec<_>>();
+
+    cels.sort_by_key(|cel| (cel.layer as i32 + cel.z_index as i32, cel.z_index));
+
+    for cel in cels {
+        let opacity = (cel.opacity as f32 / 255.0) * (layers[cel.layer].opacity as f32 / 255.0);
+
+        for (x, y, src) in cel.image.enumerate_pixels() {
+            let frame_x = cel.position.x + x as i32;
+            let frame_y = cel.position.y + y as i32;
+
+            if frame_x < 0 || frame_y < 0 || frame_x >= size.x as i32 || frame_y >= size.y as i32 {
+                continue;
+            }
+
+            let dst = frame.get_pixel_mut(frame_x as u32, frame_y as u32);
+            dst.0 = blend(src.0, dst.0, opacity);
+        }
+    }
+
+    frame
+}
+
+fn blend(src: [u8; 4], dst: [u8; 4], opacity: f32) -> [u8; 4] {
+    let src_alpha = src[3] as f32 / 255.0 * opacity;
+    let dst_alpha = dst[3] as f32 / 255.0;
+    let out_alpha = src_alpha + dst_alpha * (1.0 - src_alpha);
+
+    if out_alpha <= 0.0 {
+        return [0; 4];
+    }
+
+    let channel = |i: usize| {
+        let value = (src[i] as f32 * src_alpha + dst[i] as f32 * dst_alpha * (1.0 - src_alpha))
+            / out_alpha;
+
+        value.round() as u8
+    };
+
+    [
+        channel(0),
+        channel(1),
+        channel(2),
+        (out_alpha * 255.0).round() as u8,
+    ]
+}
+
+fn to_rgba(
+    pixel: &[u8],
+    color_depth: ColorDepth,
+    palette: &[[u8; 4]],
+    transparent_index: u8,
+) -> [u8; 4] {
+    match color_depth {
+        ColorDepth::Rgba => [pixel[0], pixel[1], pixel[2], pixel[3]],
+        ColorDepth::Grayscale => [pixel[0], pixel[0], pixel[0], pixel[1]],
+        ColorDepth::Indexed if pixel[0] == transparent_index => [0; 4],
+        ColorDepth::Indexed => palette.get(pixel[0] as usize).copied().unwrap_or([0; 4]),
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    fn slice(&self, start: usize, end: usize) -> GameResult<&'a [u8]> {
+        self.bytes
+            .get(start..end)
+            .ok_or_else(|| anyhow!("Unexpected end of Aseprite data"))
+    }
+
+    fn bytes(&mut self, len: usize) -> GameResult<&'a [u8]> {
+        let bytes = self.slice(self.offset, self.offset + len)?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn remaining(&self) -> &'a [u8] {
+        &self.bytes[self.offset.min(self.bytes.len())..]
+    }
+
+    fn skip(&mut self, len: usize) -> GameResult<()> {
+        self.bytes(len).map(|_| ())
+    }
+
+    fn u8(&mut self) -> GameResult<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> GameResult<u16> {
+        Ok(u16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn i16(&mut self) -> GameResult<i16> {
+        Ok(i16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> GameResult<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn i32(&mut self) -> GameResult<i32> {
+        Ok(i32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> GameResult<String> {
+        let len = self.u16()? as usize;
+        Ok(String::from_utf8_lossy(self.bytes(len)?).into_owned())
+    }
+
+    fn bounds(&mut self) -> GameResult<Bounds> {
+        let x = self.i32()? as f32;
+        let y = self.i32()? as f32;
+        let w = self.u32()? as f32;
+        let h = self.u32()? as f32;
+        Ok(Bounds::new(x, y, w, h))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CELS: &[u8] = include_bytes!("../../../tests/fixtures/cels.ase");
+
+    fn parse(bytes: &[u8]) -> GameResult<AseFile> {
+        AseFile::parse(bytes, &AsepriteOptions::default())
+    }
+
+    #[test]
+    fn parse_raw_cel() {
+        let file = parse(CELS).unwrap();
+
+        assert_eq!(file.size, UVec2::new(2, 2));
+        assert_eq!(file.frames.len(), 3);
+        assert_eq!(file.frame_durations, [0.1, 0.2, 0.3]);
+
+        let frame = &file.frames[0];
+        assert_eq!(frame.get_pixel(0, 0).0, [255, 0, 0, 255]);
+        assert_eq!(frame.get_pixel(1, 0).0, [0, 255, 0, 255]);
+        assert_eq!(frame.get_pixel(0, 1).0, [0, 0, 255, 255]);
+        assert_eq!(frame.get_pixel(1, 1).0, [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn parse_compressed_cel() {
+        let file = parse(CELS).unwrap();
+        let frame = &file.frames[1];
+
+        assert_eq!(frame.get_pixel(0, 0).0, [0; 4]);
+        assert_eq!(frame.get_pixel(1, 1).0, [255, 255, 0, 255]);
+    }
+
+    #[test]
+    fn parse_linked_cel() {
+        let file = parse(CELS).unwrap();
+        assert_eq!(file.frames[2], file.frames[1]);
+    }
+
+    #[test]
+    fn parse_truncated_file() {
+        assert!(parse(&CELS[..CELS.len() - 8]).is_err());
+        assert!(parse(&CELS[..HEADER_SIZE / 2]).is_err());
+    }
+}
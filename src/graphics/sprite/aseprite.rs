@@ -0,0 +1,369 @@
+use crate::game::GameResult;
+use crate::graphics::sprite::{
+    load_json_atlas, parse_json_atlas, Animation, AnimationFrame, AnimationMode, JsonAtlas,
+    Insets, SpriteSheet, Texture, TextureOptions,
+};
+use crate::graphics::{Bounds, GraphicsContext};
+use anyhow::anyhow;
+use glam::Vec2;
+use rustc_hash::FxHashMap;
+use std::path::Path;
+
+const DEFAULT_FRAME_DURATION: f32 = 0.1;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum AsepriteDirection {
+    #[default]
+    Forward,
+    Reverse,
+    PingPong,
+    PingPongReverse,
+}
+
+#[derive(Clone, Debug)]
+pub struct AsepriteTag {
+    pub name: String,
+    pub from: usize,
+    pub to: usize,
+    pub direction: AsepriteDirection,
+    pub repeat: u32,
+}
+
+impl AsepriteTag {
+    pub fn to_animation(&self, frame_durations: &[f32]) -> Animation {
+        let mut indexes = (self.from..=self.to).collect::<Vec<_>>();
+
+        if matches!(
+            self.direction,
+            AsepriteDirection::Reverse | AsepriteDirection::PingPongReverse,
+        ) {
+            indexes.reverse();
+        }
+
+        let mode = match self.direction {
+            AsepriteDirection::PingPong | AsepriteDirection::PingPongReverse => {
+                AnimationMode::PingPong
+            }
+            _ if self.repeat == 1 => AnimationMode::Once,
+            _ => AnimationMode::Loop,
+        };
+
+        let frames = indexes
+            .into_iter()
+            .map(|index| animation_frame(index, frame_durations));
+
+        Animation::new(frames).mode(mode)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AsepriteAnimation {
+    pub name: String,
+    pub frames: Vec<usize>,
+}
+
+impl AsepriteAnimation {
+    pub fn to_animation(&self, frame_durations: &[f32]) -> Animation {
+        let frames = self
+            .frames
+            .iter()
+            .map(|&index| animation_frame(index, frame_durations));
+
+        Animation::new(frames)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AsepriteSliceKey {
+    pub frame: usize,
+    pub bounds: Bounds,
+    pub center: Option<Bounds>,
+    pub pivot: Option<Vec2>,
+}
+
+impl AsepriteSliceKey {
+    pub fn insets(&self) -> Option<Insets> {
+        self.center.map(|center| Insets::from_center(self.bounds, center))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AsepriteSlice {
+    pub name: String,
+    pub keys: Vec<AsepriteSliceKey>,
+}
+
+impl AsepriteSlice {
+    pub fn key(&self, frame: usize) -> Option<&AsepriteSliceKey> {
+        self.keys.iter().rev().find(|key| key.frame <= frame)
+    }
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct AsepriteOptions {
+    pub include_hidden_layers: bool,
+    pub layer_visibility: FxHashMap<String, bool>,
+    pub texture_options: TextureOptions,
+}
+
+impl AsepriteOptions {
+    pub fn include_hidden_layers(mut self, include_hidden_layers: bool) -> Self {
+        self.include_hidden_layers = include_hidden_layers;
+        self
+    }
+
+    pub fn layer_visible<N>(mut self, name: N, visible: bool) -> Self
+    where
+        N: Into<String>,
+    {
+        self.layer_visibility.insert(name.into(), visible);
+        self
+    }
+
+    pub fn texture_options(mut self, texture_options: TextureOptions) -> Self {
+        self.texture_options = texture_options;
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Aseprite {
+    pub sprite_sheet: SpriteSheet,
+    pub frame_durations: Vec<f32>,
+    pub tags: Vec<AsepriteTag>,
+    pub animations: Vec<AsepriteAnimation>,
+    pub slices: Vec<AsepriteSlice>,
+}
+
+impl Aseprite {
+    #[cfg(feature = "aseprite")]
+    pub fn from_file<G, P>(graphics: G, path: P) -> GameResult<Self>
+    where
+        G: AsRef<GraphicsContext>,
+        P: AsRef<Path>,
+    {
+        Self::from_file_with_options(graphics, path, &AsepriteOptions::default())
+    }
+
+    #[cfg(feature = "aseprite")]
+    pub fn from_file_with_options<G, P>(
+        graphics: G,
+        path: P,
+        options: &AsepriteOptions,
+    ) -> GameResult<Self>
+    where
+        G: AsRef<GraphicsContext>,
+        P: AsRef<Path>,
+    {
+        use anyhow::Context;
+
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read Aseprite file: '{}'", path.display()))?;
+
+        Self::from_bytes_with_options(graphics, &bytes, options)
+            .with_context(|| format!("Failed to load Aseprite file: '{}'", path.display()))
+    }
+
+    #[cfg(feature = "aseprite")]
+    pub fn from_bytes<G>(graphics: G, bytes: &[u8]) -> GameResult<Self>
+    where
+        G: AsRef<GraphicsContext>,
+    {
+        Self::from_bytes_with_options(graphics, bytes, &AsepriteOptions::default())
+    }
+
+    #[cfg(feature = "aseprite")]
+    pub fn from_bytes_with_options<G>(
+        graphics: G,
+        bytes: &[u8],
+        options: &AsepriteOptions,
+    ) -> GameResult<Self>
+    where
+        G: AsRef<GraphicsContext>,
+    {
+        use crate::graphics::sprite::AseFile;
+        use image::{DynamicImage, GenericImage, RgbaImage};
+
+        let file = AseFile::parse(bytes, options)?;
+
+        let columns = (file.frames.len() as f32).sqrt().ceil().max(1.0) as u32;
+        let rows = (file.frames.len() as u32).div_ceil(columns).max(1);
+        let stride = file.size + 1;
+
+        let mut image = RgbaImage::new(
+            (columns * stride.x).saturating_sub(1).max(1),
+            (rows * stride.y).saturating_sub(1).max(1),
+        );
+
+        let mut frames = Vec::with_capacity(file.frames.len());
+
+        for (i, frame) in file.frames.iter().enumerate() {
+            let i = i as u32;
+            let x = (i % columns) * stride.x;
+            let y = (i / columns) * stride.y;
+
+            image.copy_from(frame, x, y)?;
+            frames.push(Bounds::new(
+                x as f32,
+                y as f32,
+                file.size.x as f32,
+                file.size.y as f32,
+            ));
+        }
+
+        let texture = Texture::from_image_with_options(
+            graphics,
+            &DynamicImage::ImageRgba8(image),
+            options.texture_options,
+        );
+
+        Ok(Self {
+            sprite_sheet: SpriteSheet::new(texture, frames),
+            frame_durations: file.frame_durations,
+            tags: file.tags,
+            animations: Vec::new(),
+            slices: file.slices,
+        })
+    }
+
+    pub fn from_json(texture: Texture, json: &str) -> GameResult<Self> {
+        let atlas = parse_json_atlas(json)?;
+        Self::from_json_atlas(texture, atlas)
+    }
+
+    pub fn from_json_file<G, P>(graphics: G, path: P) -> GameResult<Self>
+    where
+        G: AsRef<GraphicsContext>,
+        P: AsRef<Path>,
+    {
+        let (texture, atlas) = load_json_atlas(graphics, path.as_ref())?;
+        Self::from_json_atlas(texture, atlas)
+    }
+
+    fn from_json_atlas(texture: Texture, atlas: JsonAtlas) -> GameResult<Self> {
+        let json_frames = atlas.frames.into_frames()?;
+
+        let frame_durations = json_frames
+            .iter()
+            .map(|frame| {
+                frame
+                    .duration
+                    .map_or(DEFAULT_FRAME_DURATION, |duration| duration / 1000.0)
+            })
+            .collect();
+
+        let sprite_sheet = SpriteSheet::from_json_frames(texture, &json_frames)?;
+
+        let tags = atlas
+            .meta
+            .frame_tags
+            .iter()
+            .map(|tag| {
+                let direction = match tag.direction.as_str() {
+                    "reverse" => AsepriteDirection::Reverse,
+                    "pingpong" => AsepriteDirection::PingPong,
+                    "pingpong_reverse" => AsepriteDirection::PingPongReverse,
+                    _ => AsepriteDirection::Forward,
+                };
+
+                AsepriteTag {
+                    name: tag.name.clone(),
+                    from: tag.from,
+                    to: tag.to,
+                    direction,
+                    repeat: tag.repeat(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut animations = atlas
+            .animations
+            .into_iter()
+            .map(|(name, frame_names)| {
+                let frames = frame_names
+                    .iter()
+                    .map(|frame_name| {
+                        sprite_sheet.frame_index(frame_name).ok_or_else(|| {
+                            anyhow!("Animation '{name}' references unknown frame '{frame_name}'")
+                        })
+                    })
+                    .collect::<GameResult<Vec<_>>>()?;
+
+                Ok(AsepriteAnimation { name, frames })
+            })
+            .collect::<GameResult<Vec<_>>>()?;
+
+        animations.sort_by(|animation1, animation2| animation1.name.cmp(&animation2.name));
+
+        let slices = atlas
+            .meta
+            .slices
+            .into_iter()
+            .map(|slice| {
+                AsepriteSlice {
+                    name: slice.name,
+                    keys: slice
+                        .keys
+                        .into_iter()
+                        .map(|key| {
+                            AsepriteSliceKey {
+                                frame: key.frame,
+                                bounds: key.bounds,
+                                center: key.center,
+                                pivot: key.pivot.map(|pivot| Vec2::new(pivot.x, pivot.y)),
+                            }
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            sprite_sheet,
+            frame_durations,
+            tags,
+            animations,
+            slices,
+        })
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&AsepriteTag> {
+        self.tags.iter().find(|tag| tag.name == name)
+    }
+
+    pub fn animation(&self, name: &str) -> Option<Animation> {
+        match self.tag(name) {
+            Some(tag) => Some(tag.to_animation(&self.frame_durations)),
+            None => {
+                self.animations
+                    .iter()
+                    .find(|animation| animation.name == name)
+                    .map(|animation| animation.to_animation(&self.frame_durations))
+            }
+        }
+    }
+
+    pub fn full_animation(&self) -> Animation {
+        let frames = self
+            .frame_durations
+            .iter()
+            .enumerate()
+            .map(|(index, &duration)| AnimationFrame::new(index, duration));
+
+        Animation::new(frames)
+    }
+
+    pub fn slice(&self, name: &str) -> Option<&AsepriteSlice> {
+        self.slices.iter().find(|slice| slice.name == name)
+    }
+}
+
+fn animation_frame(index: usize, frame_durations: &[f32]) -> AnimationFrame {
+    let duration = frame_durations
+        .get(index)
+        .copied()
+        .unwrap_or(DEFAULT_FRAME_DURATION);
+
+    AnimationFrame::new(index, duration)
+}
@@ -1,12 +1,35 @@
+mod animation;
+#[cfg(feature = "aseprite")]
+mod ase_file;
+mod aseprite;
+mod nine_slice;
 mod sprite;
+mod sprite_grid;
 mod sprite_instance;
+mod sprite_sheet;
+mod sprite_sheet_json;
+mod static_sprite_batch;
 mod texture;
+mod texture_atlas;
 
+pub use self::animation::*;
+pub use self::aseprite::*;
+pub use self::nine_slice::*;
 pub use self::sprite::*;
+pub use self::sprite_grid::*;
 pub use self::sprite_instance::*;
+pub use self::sprite_sheet::*;
+pub use self::static_sprite_batch::*;
 pub use self::texture::*;
+pub use self::texture_atlas::*;
 
-use crate::graphics::{vertex_attr_array, SharedBindGroupLayouts, WgpuContext};
+#[cfg(feature = "aseprite")]
+pub(crate) use self::ase_file::*;
+pub(crate) use self::sprite_sheet_json::*;
+
+use crate::graphics::{
+    vertex_attr_array, write_buffer, SamplerOptions, SharedBindGroupLayouts, WgpuContext,
+};
 use std::mem;
 use std::ops::Range;
 use wgpu::util::DeviceExt;
@@ -14,15 +37,28 @@ use wgpu::util::DeviceExt;
 #[derive(Clone, Debug)]
 pub struct SpriteBatch {
     pub texture: Texture,
-    pub smooth: bool,
+    pub sampler: SamplerOptions,
     pub instances: Range<u32>,
 }
 
+#[derive(Clone, Debug)]
+pub struct SpriteGridBatch {
+    pub texture: Texture,
+    pub sampler: SamplerOptions,
+    pub indexes: Range<u32>,
+    pub instance: u32,
+}
+
 #[derive(Debug)]
 pub struct SpriteRenderer {
     pipeline: wgpu::RenderPipeline,
+    grid_pipeline: wgpu::RenderPipeline,
     instances: Vec<SpriteInstance>,
     instance_buffer: Option<wgpu::Buffer>,
+    grid_vertexes: Vec<SpriteGridVertex>,
+    grid_indexes: Vec<u32>,
+    grid_vertex_buffer: Option<wgpu::Buffer>,
+    grid_index_buffer: Option<wgpu::Buffer>,
 }
 
 impl SpriteRenderer {
@@ -55,12 +91,75 @@ impl SpriteRenderer {
             &shader_module,
             format,
             sample_count,
+            wgpu::VertexState {
+                module: &shader_module,
+                entry_point: "vs_main",
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: mem::size_of::<SpriteInstance>() as _,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &vertex_attr_array!(SpriteInstance {
+                        0 => size: Float32x2,
+                        1 => scale_rotation_x_axis: Float32x2,
+                        2 => scale_rotation_y_axis: Float32x2,
+                        3 => translation: Float32x2,
+                        4 => anchor_offset: Float32x2,
+                        5 => texture_size: Float32x2,
+                        6 => uv_edges: Float32x4,
+                        7 => linear_color: Float32x4,
+                        8 => top_left_linear_color: Float32x4,
+                        9 => bottom_left_linear_color: Float32x4,
+                        10 => top_right_linear_color: Float32x4,
+                        11 => bottom_right_linear_color: Float32x4,
+                    }),
+                }],
+            },
+            wgpu::PrimitiveTopology::TriangleStrip,
+        );
+
+        let grid_pipeline = Self::create_pipeline(
+            device,
+            &pipeline_layout,
+            &shader_module,
+            format,
+            sample_count,
+            wgpu::VertexState {
+                module: &shader_module,
+                entry_point: "vs_grid",
+                buffers: &[
+                    wgpu::VertexBufferLayout {
+                        array_stride: mem::size_of::<SpriteGridVertex>() as _,
+                        step_mode: wgpu::VertexStepMode::Vertex,
+                        attributes: &vertex_attr_array!(SpriteGridVertex {
+                            0 => position: Float32x2,
+                            1 => uv: Float32x2,
+                            2 => linear_color: Float32x4,
+                        }),
+                    },
+                    wgpu::VertexBufferLayout {
+                        array_stride: mem::size_of::<SpriteInstance>() as _,
+                        step_mode: wgpu::VertexStepMode::Instance,
+                        attributes: &vertex_attr_array!(SpriteInstance {
+                            3 => scale_rotation_x_axis: Float32x2,
+                            4 => scale_rotation_y_axis: Float32x2,
+                            5 => translation: Float32x2,
+                            6 => anchor_offset: Float32x2,
+                            7 => linear_color: Float32x4,
+                        }),
+                    },
+                ],
+            },
+            wgpu::PrimitiveTopology::TriangleList,
         );
 
         Self {
             pipeline,
+            grid_pipeline,
             instances: Vec::new(),
             instance_buffer: None,
+            grid_vertexes: Vec::new(),
+            grid_indexes: Vec::new(),
+            grid_vertex_buffer: None,
+            grid_index_buffer: None,
         }
     }
 
@@ -70,30 +169,15 @@ impl SpriteRenderer {
         shader_module: &wgpu::ShaderModule,
         texture_format: wgpu::TextureFormat,
         sample_count: u32,
+        vertex: wgpu::VertexState,
+        topology: wgpu::PrimitiveTopology,
     ) -> wgpu::RenderPipeline {
         device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
             label: Some("sprite_pipeline"),
             layout: Some(pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: shader_module,
-                entry_point: "vs_main",
-                buffers: &[wgpu::VertexBufferLayout {
-                    array_stride: mem::size_of::<SpriteInstance>() as _,
-                    step_mode: wgpu::VertexStepMode::Instance,
-                    attributes: &vertex_attr_array!(SpriteInstance {
-                        0 => size: Float32x2,
-                        1 => scale_rotation_x_axis: Float32x2,
-                        2 => scale_rotation_y_axis: Float32x2,
-                        3 => translation: Float32x2,
-                        4 => anchor_offset: Float32x2,
-                        5 => texture_size: Float32x2,
-                        6 => uv_edges: Float32x4,
-                        7 => linear_color: Float32x4,
-                    }),
-                }],
-            },
+            vertex,
             primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                topology,
                 strip_index_format: None,
                 front_face: wgpu::FrontFace::Ccw,
                 cull_mode: None,
@@ -122,13 +206,52 @@ impl SpriteRenderer {
 
     pub fn begin(&mut self) {
         self.instances.clear();
+        self.grid_vertexes.clear();
+        self.grid_indexes.clear();
     }
 
     pub fn add(&mut self, instance: SpriteInstance) {
         self.instances.push(instance);
     }
 
+    pub fn add_grid(
+        &mut self,
+        texture: Texture,
+        sampler: SamplerOptions,
+        grid: &SpriteGrid,
+        instance: SpriteInstance,
+    ) -> SpriteGridBatch {
+        let index_start = self.grid_indexes.len() as u32;
+        grid.add_geometry(&instance, &mut self.grid_vertexes, &mut self.grid_indexes);
+
+        let batch = SpriteGridBatch {
+            texture,
+            sampler,
+            indexes: index_start..(self.grid_indexes.len() as u32),
+            instance: self.instances.len() as u32,
+        };
+
+        self.instances.push(instance);
+        batch
+    }
+
     pub fn end(&mut self, wgpu: &WgpuContext) {
+        write_buffer(
+            wgpu,
+            &mut self.grid_vertex_buffer,
+            "sprite_grid_vertex_buffer",
+            wgpu::BufferUsages::VERTEX,
+            bytemuck::cast_slice(&self.grid_vertexes),
+        );
+
+        write_buffer(
+            wgpu,
+            &mut self.grid_index_buffer,
+            "sprite_grid_index_buffer",
+            wgpu::BufferUsages::INDEX,
+            bytemuck::cast_slice(&self.grid_indexes),
+        );
+
         if self.instances.is_empty() {
             return;
         }
@@ -160,12 +283,12 @@ impl SpriteRenderer {
         self.instances.len() as _
     }
 
-    pub fn next_batch(&self, texture: Texture, smooth: bool) -> SpriteBatch {
+    pub fn next_batch(&self, texture: Texture, sampler: SamplerOptions) -> SpriteBatch {
         let instance_count = self.instances.len() as u32;
 
         SpriteBatch {
             texture,
-            smooth,
+            sampler,
             instances: instance_count..(instance_count + 1),
         }
     }
@@ -179,6 +302,46 @@ impl SpriteRenderer {
         pass.set_vertex_buffer(0, instance_buffer.slice(..));
     }
 
+    pub fn prepare_static_pipeline<'a>(
+        &'a self,
+        pass: &mut wgpu::RenderPass<'a>,
+        instance_buffer: &'a wgpu::Buffer,
+    ) {
+        pass.set_pipeline(&self.pipeline);
+        pass.set_vertex_buffer(0, instance_buffer.slice(..));
+    }
+
+    pub fn prepare_grid_pipeline<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>) {
+        let (Some(vertex_buffer), Some(index_buffer), Some(instance_buffer)) = (
+            self.grid_vertex_buffer.as_ref(),
+            self.grid_index_buffer.as_ref(),
+            self.instance_buffer.as_ref(),
+        ) else {
+            return;
+        };
+
+        pass.set_pipeline(&self.grid_pipeline);
+        pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        pass.set_vertex_buffer(1, instance_buffer.slice(..));
+        pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+    }
+
+    pub fn draw_grid<'a>(
+        &'a self,
+        pass: &mut wgpu::RenderPass<'a>,
+        texture_bind_group: &'a wgpu::BindGroup,
+        sampler_bind_group: &'a wgpu::BindGroup,
+        batch: &SpriteGridBatch,
+    ) {
+        pass.set_bind_group(1, texture_bind_group, &[]);
+        pass.set_bind_group(2, sampler_bind_group, &[]);
+        pass.draw_indexed(
+            batch.indexes.clone(),
+            0,
+            batch.instance..(batch.instance + 1),
+        );
+    }
+
     pub fn draw<'a>(
         &'a self,
         pass: &mut wgpu::RenderPass<'a>,
@@ -0,0 +1,281 @@
+use crate::graphics::sprite::{SpriteInstance, Texture};
+use crate::graphics::{
+    impl_drawable_methods, Bounds, Canvas, Color, Drawable, SamplerOptions, Transform,
+};
+use glam::{Vec2, Vec4};
+
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub struct Insets {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl Insets {
+    pub const fn new(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    pub const fn uniform(inset: f32) -> Self {
+        Self::new(inset, inset, inset, inset)
+    }
+
+    pub fn from_center(bounds: Bounds, center: Bounds) -> Self {
+        Self {
+            left: center.x,
+            top: center.y,
+            right: bounds.w - center.x - center.w,
+            bottom: bounds.h - center.y - center.h,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum NineSliceMode {
+    #[default]
+    Stretch,
+    Tile,
+}
+
+#[derive(Clone, Debug)]
+pub struct NineSlice<'a> {
+    pub texture: &'a Texture,
+    pub sampler: SamplerOptions,
+    pub uv_bounds: Bounds,
+    pub insets: Insets,
+    pub size: Vec2,
+    pub edge_mode: NineSliceMode,
+    pub center_mode: NineSliceMode,
+    pub draw_center: bool,
+    pub transform: Transform,
+    pub anchor_offset: Vec2,
+    pub color: Color,
+}
+
+impl_drawable_methods!(NineSlice<'_>);
+
+impl<'a> NineSlice<'a> {
+    pub fn new<S>(texture: &'a Texture, insets: Insets, size: S) -> Self
+    where
+        S: Into<Vec2>,
+    {
+        let texture_size = texture.size().as_vec2();
+
+        Self {
+            texture,
+            sampler: SamplerOptions::NEAREST,
+            uv_bounds: Bounds::new(0.0, 0.0, texture_size.x, texture_size.y),
+            insets,
+            size: size.into(),
+            edge_mode: NineSliceMode::Stretch,
+            center_mode: NineSliceMode::Stretch,
+            draw_center: true,
+            transform: Transform::IDENTITY,
+            anchor_offset: Vec2::ZERO,
+            color: Color::WHITE,
+        }
+    }
+
+    pub fn smooth(mut self, smooth: bool) -> Self {
+        self.sampler = if smooth {
+            SamplerOptions::LINEAR
+        } else {
+            SamplerOptions::NEAREST
+        };
+
+        self
+    }
+
+    pub fn sampler(mut self, sampler: SamplerOptions) -> Self {
+        self.sampler = sampler;
+        self
+    }
+
+    pub fn uv_bounds<B>(mut self, uv_bounds: B) -> Self
+    where
+        B: Into<Bounds>,
+    {
+        self.uv_bounds = uv_bounds.into();
+        self
+    }
+
+    pub fn edge_mode(mut self, edge_mode: NineSliceMode) -> Self {
+        self.edge_mode = edge_mode;
+        self
+    }
+
+    pub fn center_mode(mut self, center_mode: NineSliceMode) -> Self {
+        self.center_mode = center_mode;
+        self
+    }
+
+    pub fn draw_center(mut self, draw_center: bool) -> Self {
+        self.draw_center = draw_center;
+        self
+    }
+
+    pub fn anchor_center(mut self) -> Self {
+        self.anchor_offset = self.size * 0.5;
+        self
+    }
+
+    pub fn relative_anchor<A>(mut self, relative_anchor: A) -> Self
+    where
+        A: Into<Vec2>,
+    {
+        self.anchor_offset = self.size * relative_anchor.into();
+        self
+    }
+
+    pub fn to_sprite_instances(&self) -> Vec<SpriteInstance> {
+        let insets = self.insets;
+
+        let (source_columns, target_columns) = slice_axis(
+            self.uv_bounds.w,
+            insets.left,
+            insets.right,
+            self.size.x,
+        );
+
+        let (source_rows, target_rows) = slice_axis(
+            self.uv_bounds.h,
+            insets.top,
+            insets.bottom,
+            self.size.y,
+        );
+
+        let affine2 = self.transform.to_affine2();
+        let texture_size = self.texture.size().as_vec2();
+        let linear_color = self.color.to_linear_vec4();
+        let mut instances = Vec::new();
+
+        for row in 0..3 {
+            for column in 0..3 {
+                let is_center = row == 1 && column == 1;
+
+                if is_center && !self.draw_center {
+                    continue;
+                }
+
+                let source = Bounds::new(
+                    self.uv_bounds.x + source_columns[column].0,
+                    self.uv_bounds.y + source_rows[row].0,
+                    source_columns[column].1,
+                    source_rows[row].1,
+                );
+
+                let target = Bounds::new(
+                    target_columns[column].0,
+                    target_rows[row].0,
+                    target_columns[column].1,
+                    target_rows[row].1,
+                );
+
+                if source.w <= 0.0 || source.h <= 0.0 || target.w <= 0.0 || target.h <= 0.0 {
+                    continue;
+                }
+
+                let mode = if is_center {
+                    self.center_mode
+                } else {
+                    self.edge_mode
+                };
+
+                let tile_x = mode == NineSliceMode::Tile && column == 1;
+                let tile_y = mode == NineSliceMode::Tile && row == 1;
+
+                let tile_w = if tile_x { source.w } else { target.w };
+                let tile_h = if tile_y { source.h } else { target.h };
+
+                let mut y = 0.0;
+
+                while y < target.h {
+                    let h = tile_h.min(target.h - y);
+                    let uv_h = if tile_y { h } else { source.h };
+                    let mut x = 0.0;
+
+                    while x < target.w {
+                        let w = tile_w.min(target.w - x);
+                        let uv_w = if tile_x { w } else { source.w };
+
+                        instances.push(SpriteInstance {
+                            size: Vec2::new(w, h),
+                            scale_rotation_x_axis: affine2.matrix2.x_axis,
+                            scale_rotation_y_axis: affine2.matrix2.y_axis,
+                            translation: affine2.translation,
+                            anchor_offset: self.anchor_offset
+                                - Vec2::new(target.x + x, target.y + y),
+                            texture_size,
+                            uv_edges: Vec4::new(
+                                source.y,
+                                source.x,
+                                source.y + uv_h,
+                                source.x + uv_w,
+                            ),
+                            linear_color,
+                            ..Default::default()
+                        });
+
+                        x += w;
+                    }
+
+                    y += h;
+                }
+            }
+        }
+
+        instances
+    }
+}
+
+impl Drawable for NineSlice<'_> {
+    fn draw(self, canvas: &mut Canvas) {
+        for instance in self.to_sprite_instances() {
+            canvas.draw_sprite(self.texture, self.sampler, instance);
+        }
+    }
+}
+
+type AxisSlices = [(f32, f32); 3];
+
+fn slice_axis(
+    source_size: f32,
+    start: f32,
+    end: f32,
+    target_size: f32,
+) -> (AxisSlices, AxisSlices) {
+    let start = start.clamp(0.0, source_size);
+    let end = end.clamp(0.0, source_size - start);
+    let source_middle = source_size - start - end;
+
+    let target_size = target_size.max(0.0);
+    let border_scale = if start + end > target_size {
+        target_size / (start + end)
+    } else {
+        1.0
+    };
+
+    let target_start = start * border_scale;
+    let target_end = end * border_scale;
+    let target_middle = target_size - target_start - target_end;
+
+    let source = [
+        (0.0, start),
+        (start, source_middle),
+        (start + source_middle, end),
+    ];
+
+    let target = [
+        (0.0, target_start),
+        (target_start, target_middle),
+        (target_start + target_middle, target_end),
+    ];
+
+    (source, target)
+}
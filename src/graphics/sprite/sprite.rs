@@ -1,20 +1,23 @@
-use crate::graphics::sprite::{SpriteInstance, Texture};
+use crate::graphics::sprite::{SpriteGrid, SpriteInstance, Texture};
 use crate::graphics::{
-    impl_drawable_methods, AsDrawable, Bounds, Canvas, Color, Drawable, Transform,
+    impl_drawable_methods, AsDrawable, Bounds, Canvas, Color, Drawable, SamplerOptions, Transform,
 };
-use glam::{Vec2, Vec4};
+use glam::{Mat2, Vec2, Vec4};
 
 #[derive(Clone, Debug)]
 pub struct Sprite<'a> {
     pub texture: &'a Texture,
-    pub smooth: bool,
+    pub sampler: SamplerOptions,
     pub custom_size: Option<Vec2>,
     pub uv_bounds: Bounds,
     pub flip_x: bool,
     pub flip_y: bool,
     pub transform: Transform,
+    pub skew: Vec2,
     pub anchor_offset: Vec2,
     pub color: Color,
+    pub corner_colors: [Color; 4],
+    pub grid: Option<&'a SpriteGrid>,
 }
 
 impl_drawable_methods!(Sprite<'_>);
@@ -25,17 +28,35 @@ impl<'a> Sprite<'a> {
 
         Self {
             texture,
-            smooth: false,
+            sampler: SamplerOptions::NEAREST,
             custom_size: None,
             uv_bounds: Bounds::new(0.0, 0.0, texture_size.x, texture_size.y),
             flip_x: false,
             flip_y: false,
             transform: Transform::IDENTITY,
+            skew: Vec2::ZERO,
             anchor_offset: Vec2::ZERO,
             color: Color::WHITE,
+            corner_colors: [Color::WHITE; 4],
+            grid: None,
         }
     }
 
+    pub fn smooth(mut self, smooth: bool) -> Self {
+        self.sampler = if smooth {
+            SamplerOptions::LINEAR
+        } else {
+            SamplerOptions::NEAREST
+        };
+
+        self
+    }
+
+    pub fn sampler(mut self, sampler: SamplerOptions) -> Self {
+        self.sampler = sampler;
+        self
+    }
+
     pub fn custom_size<S>(mut self, size: S) -> Self
     where
         S: Into<Vec2>,
@@ -75,6 +96,34 @@ impl<'a> Sprite<'a> {
         self
     }
 
+    pub fn skew<S>(mut self, skew: S) -> Self
+    where
+        S: Into<Vec2>,
+    {
+        self.skew = skew.into();
+        self
+    }
+
+    pub fn corner_colors(mut self, corner_colors: [Color; 4]) -> Self {
+        self.corner_colors = corner_colors;
+        self
+    }
+
+    pub fn vertical_gradient(mut self, top: Color, bottom: Color) -> Self {
+        self.corner_colors = [top, top, bottom, bottom];
+        self
+    }
+
+    pub fn horizontal_gradient(mut self, left: Color, right: Color) -> Self {
+        self.corner_colors = [left, right, left, right];
+        self
+    }
+
+    pub fn grid(mut self, grid: &'a SpriteGrid) -> Self {
+        self.grid = Some(grid);
+        self
+    }
+
     fn size(&self) -> Vec2 {
         self.custom_size.unwrap_or_else(|| self.uv_bounds.size())
     }
@@ -82,6 +131,15 @@ impl<'a> Sprite<'a> {
     pub fn to_sprite_instance(&self) -> SpriteInstance {
         let affine2 = self.transform.to_affine2();
 
+        let scale_rotation_skew = affine2.matrix2
+            * Mat2::from_cols(
+                Vec2::new(1.0, self.skew.y.tan()),
+                Vec2::new(self.skew.x.tan(), 1.0),
+            );
+
+        let [top_left, top_right, bottom_left, bottom_right] =
+            self.corner_colors.map(|color| color.to_linear_vec4());
+
         let (left, right) = if self.flip_x {
             (self.uv_bounds.x + self.uv_bounds.w, self.uv_bounds.x)
         } else {
@@ -96,20 +154,29 @@ impl<'a> Sprite<'a> {
 
         SpriteInstance {
             size: self.size(),
-            scale_rotation_x_axis: affine2.matrix2.x_axis,
-            scale_rotation_y_axis: affine2.matrix2.y_axis,
+            scale_rotation_x_axis: scale_rotation_skew.x_axis,
+            scale_rotation_y_axis: scale_rotation_skew.y_axis,
             translation: affine2.translation,
             anchor_offset: self.anchor_offset,
             texture_size: self.texture.size().as_vec2(),
             uv_edges: Vec4::new(top, left, bottom, right),
             linear_color: self.color.to_linear_vec4(),
+            top_left_linear_color: top_left,
+            bottom_left_linear_color: bottom_left,
+            top_right_linear_color: top_right,
+            bottom_right_linear_color: bottom_right,
         }
     }
 }
 
 impl Drawable for Sprite<'_> {
     fn draw(self, canvas: &mut Canvas) {
-        canvas.draw_sprite(self.texture, self.smooth, self.to_sprite_instance());
+        let instance = self.to_sprite_instance();
+
+        match self.grid {
+            Some(grid) => canvas.draw_sprite_grid(self.texture, self.sampler, grid, instance),
+            None => canvas.draw_sprite(self.texture, self.sampler, instance),
+        }
     }
 }
 
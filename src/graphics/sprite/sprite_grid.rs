@@ -0,0 +1,145 @@
+use crate::graphics::sprite::SpriteInstance;
+use bytemuck::{Pod, Zeroable};
+use glam::{Vec2, Vec4};
+
+#[repr(C)]
+#[derive(Clone, Copy, Default, Debug, Pod, Zeroable)]
+pub(crate) struct SpriteGridVertex {
+    pub position: Vec2,
+    pub uv: Vec2,
+    pub linear_color: Vec4,
+}
+
+#[derive(Clone, Debug)]
+pub struct SpriteGrid {
+    columns: u32,
+    rows: u32,
+    offsets: Vec<Vec2>,
+}
+
+impl SpriteGrid {
+    pub fn new(columns: u32, rows: u32) -> Self {
+        let columns = columns.max(1);
+        let rows = rows.max(1);
+
+        Self {
+            columns,
+            rows,
+            offsets: vec![Vec2::ZERO; ((columns + 1) * (rows + 1)) as usize],
+        }
+    }
+
+    pub fn columns(&self) -> u32 {
+        self.columns
+    }
+
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    pub fn offsets(&self) -> &[Vec2] {
+        &self.offsets
+    }
+
+    pub fn offsets_mut(&mut self) -> &mut [Vec2] {
+        &mut self.offsets
+    }
+
+    pub fn offset(&self, column: u32, row: u32) -> Vec2 {
+        self.offsets[self.index(column, row)]
+    }
+
+    pub fn set_offset<O>(&mut self, column: u32, row: u32, offset: O)
+    where
+        O: Into<Vec2>,
+    {
+        let index = self.index(column, row);
+        self.offsets[index] = offset.into();
+    }
+
+    pub fn set_offsets_with<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Vec2) -> Vec2,
+    {
+        for row in 0..=self.rows {
+            for column in 0..=self.columns {
+                let index = self.index(column, row);
+                self.offsets[index] = f(self.normalized_position(column, row));
+            }
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.offsets.fill(Vec2::ZERO);
+    }
+
+    pub(crate) fn add_geometry(
+        &self,
+        instance: &SpriteInstance,
+        vertexes: &mut Vec<SpriteGridVertex>,
+        indexes: &mut Vec<u32>,
+    ) {
+        let base = vertexes.len() as u32;
+        let [top, left, bottom, right] = instance.uv_edges.to_array();
+
+        for row in 0..=self.rows {
+            for column in 0..=self.columns {
+                let t = self.normalized_position(column, row);
+
+                let uv = Vec2::new(
+                    left + (right - left) * t.x,
+                    top + (bottom - top) * t.y,
+                ) / instance.texture_size;
+
+                let top_color = instance
+                    .top_left_linear_color
+                    .lerp(instance.top_right_linear_color, t.x);
+
+                let bottom_color = instance
+                    .bottom_left_linear_color
+                    .lerp(instance.bottom_right_linear_color, t.x);
+
+                vertexes.push(SpriteGridVertex {
+                    position: instance.size * t + self.offset(column, row),
+                    uv,
+                    linear_color: top_color.lerp(bottom_color, t.y),
+                });
+            }
+        }
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let top_left = base + self.index(column, row) as u32;
+                let bottom_left = base + self.index(column, row + 1) as u32;
+                let top_right = top_left + 1;
+                let bottom_right = bottom_left + 1;
+
+                indexes.extend_from_slice(&[
+                    top_left,
+                    bottom_left,
+                    top_right,
+                    top_right,
+                    bottom_left,
+                    bottom_right,
+                ]);
+            }
+        }
+    }
+
+    fn index(&self, column: u32, row: u32) -> usize {
+        assert!(
+            column <= self.columns && row <= self.rows,
+            "Sprite grid point out of bounds",
+        );
+
+        (row * (self.columns + 1) + column) as usize
+    }
+
+    fn normalized_position(&self, column: u32, row: u32) -> Vec2 {
+        Vec2::new(
+            column as f32 / self.columns as f32,
+            row as f32 / self.rows as f32,
+        )
+    }
+}
+
@@ -12,6 +12,10 @@ pub struct SpriteInstance {
     pub texture_size: Vec2,
     pub uv_edges: Vec4, // top, left, bottom, right
     pub linear_color: Vec4,
+    pub top_left_linear_color: Vec4,
+    pub bottom_left_linear_color: Vec4,
+    pub top_right_linear_color: Vec4,
+    pub bottom_right_linear_color: Vec4,
 }
 
 impl Default for SpriteInstance {
@@ -25,6 +29,10 @@ impl Default for SpriteInstance {
             texture_size: Vec2::ONE,
             uv_edges: Vec4::new(0.0, 0.0, 1.0, 1.0),
             linear_color: Vec4::ONE,
+            top_left_linear_color: Vec4::ONE,
+            bottom_left_linear_color: Vec4::ONE,
+            top_right_linear_color: Vec4::ONE,
+            bottom_right_linear_color: Vec4::ONE,
         }
     }
 }
@@ -0,0 +1,206 @@
+use crate::game::GameResult;
+use crate::graphics::sprite::{load_json_atlas, parse_json_atlas, JsonFrame, Sprite, Texture};
+use crate::graphics::{Bounds, GraphicsContext};
+use anyhow::ensure;
+use glam::{UVec2, Vec2};
+use rustc_hash::FxHashMap;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug)]
+pub struct SpriteSheetGrid {
+    pub cell_size: UVec2,
+    pub margin: UVec2,
+    pub spacing: UVec2,
+    pub columns: Option<u32>,
+    pub rows: Option<u32>,
+}
+
+impl SpriteSheetGrid {
+    pub fn new<S>(cell_size: S) -> Self
+    where
+        S: Into<UVec2>,
+    {
+        Self {
+            cell_size: cell_size.into().max(UVec2::ONE),
+            margin: UVec2::ZERO,
+            spacing: UVec2::ZERO,
+            columns: None,
+            rows: None,
+        }
+    }
+
+    pub fn margin<M>(mut self, margin: M) -> Self
+    where
+        M: Into<UVec2>,
+    {
+        self.margin = margin.into();
+        self
+    }
+
+    pub fn spacing<S>(mut self, spacing: S) -> Self
+    where
+        S: Into<UVec2>,
+    {
+        self.spacing = spacing.into();
+        self
+    }
+
+    pub fn columns(mut self, columns: u32) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    pub fn rows(mut self, rows: u32) -> Self {
+        self.rows = Some(rows);
+        self
+    }
+
+    fn frames(&self, texture_size: UVec2) -> Vec<Bounds> {
+        let stride = self.cell_size + self.spacing;
+        let available = texture_size.saturating_sub(self.margin) + self.spacing;
+        let max_cells = available / stride;
+
+        let columns = self.columns.map_or(max_cells.x, |columns| columns.min(max_cells.x));
+        let rows = self.rows.map_or(max_cells.y, |rows| rows.min(max_cells.y));
+
+        (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| UVec2::new(column, row)))
+            .map(|cell| {
+                let position = (self.margin + cell * stride).as_vec2();
+                let size = self.cell_size.as_vec2();
+                Bounds::new(position.x, position.y, size.x, size.y)
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SpriteSheet {
+    texture: Texture,
+    frames: Vec<Bounds>,
+    frame_offsets: Vec<Vec2>,
+    frame_names: FxHashMap<String, usize>,
+}
+
+impl SpriteSheet {
+    pub fn new<F>(texture: Texture, frames: F) -> Self
+    where
+        F: IntoIterator<Item = Bounds>,
+    {
+        let frames = frames.into_iter().collect::<Vec<_>>();
+
+        Self {
+            texture,
+            frame_offsets: vec![Vec2::ZERO; frames.len()],
+            frames,
+            frame_names: FxHashMap::default(),
+        }
+    }
+
+    pub fn from_grid(texture: Texture, grid: SpriteSheetGrid) -> Self {
+        let frames = grid.frames(texture.size());
+        Self::new(texture, frames)
+    }
+
+    pub fn from_json(texture: Texture, json: &str) -> GameResult<Self> {
+        let atlas = parse_json_atlas(json)?;
+        Self::from_json_frames(texture, &atlas.frames.into_frames()?)
+    }
+
+    pub fn from_json_file<G, P>(graphics: G, path: P) -> GameResult<Self>
+    where
+        G: AsRef<GraphicsContext>,
+        P: AsRef<Path>,
+    {
+        let (texture, atlas) = load_json_atlas(graphics, path.as_ref())?;
+        Self::from_json_frames(texture, &atlas.frames.into_frames()?)
+    }
+
+    pub(crate) fn from_json_frames(
+        texture: Texture,
+        json_frames: &[JsonFrame],
+    ) -> GameResult<Self> {
+        let mut sprite_sheet = Self::new(texture, []);
+
+        for json_frame in json_frames {
+            ensure!(
+                !json_frame.rotated,
+                "Rotated sprite sheet frames are not supported: '{}'",
+                json_frame.filename.as_deref().unwrap_or_default(),
+            );
+
+            sprite_sheet.push_trimmed_frame(
+                json_frame.filename.as_deref(),
+                json_frame.frame,
+                json_frame.trim_offset(),
+            );
+        }
+
+        Ok(sprite_sheet)
+    }
+
+    pub fn push_frame<N>(&mut self, name: Option<N>, bounds: Bounds) -> usize
+    where
+        N: Into<String>,
+    {
+        self.push_trimmed_frame(name, bounds, Vec2::ZERO)
+    }
+
+    pub fn push_trimmed_frame<N, O>(&mut self, name: Option<N>, bounds: Bounds, offset: O) -> usize
+    where
+        N: Into<String>,
+        O: Into<Vec2>,
+    {
+        let index = self.frames.len();
+        self.frames.push(bounds);
+        self.frame_offsets.push(offset.into());
+
+        if let Some(name) = name {
+            self.frame_names.insert(name.into(), index);
+        }
+
+        index
+    }
+
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    pub fn frames(&self) -> &[Bounds] {
+        &self.frames
+    }
+
+    pub fn frame(&self, index: usize) -> Bounds {
+        self.frames[index]
+    }
+
+    pub fn frame_offset(&self, index: usize) -> Vec2 {
+        self.frame_offsets[index]
+    }
+
+    pub fn frame_index(&self, name: &str) -> Option<usize> {
+        self.frame_names.get(name).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn sprite(&self, index: usize) -> Sprite<'_> {
+        Sprite::new(&self.texture)
+            .uv_bounds(self.frames[index])
+            .pixel_anchor(-self.frame_offsets[index])
+    }
+
+    pub fn get(&self, index: usize) -> Option<Sprite<'_>> {
+        (index < self.frames.len()).then(|| self.sprite(index))
+    }
+
+    pub fn named_sprite(&self, name: &str) -> Option<Sprite<'_>> {
+        self.frame_index(name).map(|index| self.sprite(index))
+    }
+}
@@ -0,0 +1,154 @@
+use crate::game::GameResult;
+use crate::graphics::sprite::Texture;
+use crate::graphics::{Bounds, GraphicsContext};
+use anyhow::{anyhow, Context};
+use glam::Vec2;
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+#[derive(Deserialize)]
+pub(crate) struct JsonAtlas {
+    pub frames: JsonFrames,
+    #[serde(default)]
+    pub animations: FxHashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub meta: JsonMeta,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum JsonFrames {
+    Array(Vec<JsonFrame>),
+    Hash(serde_json::Map<String, serde_json::Value>),
+}
+
+impl JsonFrames {
+    pub fn into_frames(self) -> GameResult<Vec<JsonFrame>> {
+        match self {
+            Self::Array(frames) => Ok(frames),
+            Self::Hash(frames) => {
+                frames
+                    .into_iter()
+                    .map(|(name, value)| {
+                        let mut frame = serde_json::from_value::<JsonFrame>(value)
+                            .map_err(|error| anyhow!("Failed to parse frame '{name}': {error}"))?;
+
+                        frame.filename = Some(name);
+                        Ok(frame)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct JsonFrame {
+    #[serde(default)]
+    pub filename: Option<String>,
+    pub frame: Bounds,
+    #[serde(default)]
+    pub rotated: bool,
+    #[serde(default)]
+    pub trimmed: bool,
+    #[serde(default, rename = "spriteSourceSize")]
+    pub sprite_source_size: Option<Bounds>,
+    #[serde(default)]
+    pub duration: Option<f32>,
+}
+
+impl JsonFrame {
+    pub fn trim_offset(&self) -> Vec2 {
+        match self.sprite_source_size {
+            Some(bounds) if self.trimmed => bounds.position(),
+            _ => Vec2::ZERO,
+        }
+    }
+}
+
+#[derive(Default, Deserialize)]
+pub(crate) struct JsonMeta {
+    #[serde(default)]
+    pub image: Option<String>,
+    #[serde(default, rename = "frameTags")]
+    pub frame_tags: Vec<JsonFrameTag>,
+    #[serde(default)]
+    pub slices: Vec<JsonSlice>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct JsonFrameTag {
+    pub name: String,
+    pub from: usize,
+    pub to: usize,
+    #[serde(default)]
+    pub direction: String,
+    #[serde(default)]
+    pub repeat: Option<serde_json::Value>,
+}
+
+impl JsonFrameTag {
+    pub fn repeat(&self) -> u32 {
+        match self.repeat.as_ref() {
+            Some(serde_json::Value::Number(repeat)) => repeat.as_u64().unwrap_or(0) as u32,
+            Some(serde_json::Value::String(repeat)) => repeat.parse().unwrap_or(0),
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct JsonSlice {
+    pub name: String,
+    #[serde(default)]
+    pub keys: Vec<JsonSliceKey>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct JsonSliceKey {
+    pub frame: usize,
+    pub bounds: Bounds,
+    #[serde(default)]
+    pub center: Option<Bounds>,
+    #[serde(default)]
+    pub pivot: Option<JsonPoint>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct JsonPoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+pub(crate) fn parse_json_atlas(json: &str) -> GameResult<JsonAtlas> {
+    serde_json::from_str::<JsonAtlas>(json)
+        .map_err(|error| anyhow!("Failed to parse sprite sheet JSON: {error}"))
+}
+
+pub(crate) fn load_json_atlas<G>(graphics: G, path: &Path) -> GameResult<(Texture, JsonAtlas)>
+where
+    G: AsRef<GraphicsContext>,
+{
+    let json = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read sprite sheet file: '{}'", path.display()))?;
+
+    let atlas = serde_json::from_str::<JsonAtlas>(&json).map_err(|error| {
+        anyhow!(
+            "Failed to parse sprite sheet file: '{}': {error}",
+            path.display(),
+        )
+    })?;
+
+    let image = atlas.meta.image.as_ref().ok_or_else(|| {
+        anyhow!(
+            "Sprite sheet file does not reference an image: '{}'",
+            path.display(),
+        )
+    })?;
+
+    let image_path = path.parent().unwrap_or(Path::new("")).join(image);
+    let texture = Texture::from_file(graphics, image_path)?;
+    Ok((texture, atlas))
+}
@@ -0,0 +1,169 @@
+use crate::graphics::sprite::{Sprite, SpriteInstance, Texture};
+use crate::graphics::{Canvas, Drawable, SamplerOptions, WgpuContext};
+use std::mem;
+use std::ops::Range;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+
+#[derive(Clone, Debug)]
+pub(crate) struct StaticSpriteBatchDraw {
+    pub instance_buffer: Arc<wgpu::Buffer>,
+    pub texture: Texture,
+    pub sampler: SamplerOptions,
+    pub instances: Range<u32>,
+}
+
+#[derive(Debug)]
+pub struct StaticSpriteBatch {
+    texture: Texture,
+    sampler: SamplerOptions,
+    instances: Vec<SpriteInstance>,
+    instance_buffer: Option<Arc<wgpu::Buffer>>,
+    dirty: bool,
+}
+
+impl StaticSpriteBatch {
+    pub fn new(texture: Texture) -> Self {
+        Self {
+            texture,
+            sampler: SamplerOptions::NEAREST,
+            instances: Vec::new(),
+            instance_buffer: None,
+            dirty: false,
+        }
+    }
+
+    pub fn smooth(mut self, smooth: bool) -> Self {
+        self.sampler = if smooth {
+            SamplerOptions::LINEAR
+        } else {
+            SamplerOptions::NEAREST
+        };
+
+        self
+    }
+
+    pub fn sampler(mut self, sampler: SamplerOptions) -> Self {
+        self.sampler = sampler;
+        self
+    }
+
+    pub fn set_sampler(&mut self, sampler: SamplerOptions) {
+        self.sampler = sampler;
+    }
+
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    pub fn instances(&self) -> &[SpriteInstance] {
+        &self.instances
+    }
+
+    pub fn len(&self) -> usize {
+        self.instances.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instances.is_empty()
+    }
+
+    pub fn push(&mut self, instance: SpriteInstance) -> usize {
+        self.instances.push(instance);
+        self.dirty = true;
+        self.instances.len() - 1
+    }
+
+    pub fn push_sprite(&mut self, sprite: &Sprite) -> usize {
+        assert!(
+            sprite.texture == &self.texture,
+            "Sprite texture does not match the batch's texture",
+        );
+
+        self.push(sprite.to_sprite_instance())
+    }
+
+    pub fn set(&mut self, index: usize, instance: SpriteInstance) {
+        self.instances[index] = instance;
+        self.dirty = true;
+    }
+
+    pub fn remove(&mut self, index: usize) -> SpriteInstance {
+        self.dirty = true;
+        self.instances.swap_remove(index)
+    }
+
+    pub fn clear(&mut self) {
+        self.instances.clear();
+        self.dirty = true;
+    }
+
+    pub fn upload<W>(&mut self, wgpu: W)
+    where
+        W: AsRef<WgpuContext>,
+    {
+        if !self.dirty {
+            return;
+        }
+
+        self.dirty = false;
+
+        if self.instances.is_empty() {
+            return;
+        }
+
+        let wgpu = wgpu.as_ref();
+        let instances_size =
+            (self.instances.len() * mem::size_of::<SpriteInstance>()) as wgpu::BufferAddress;
+
+        match self.instance_buffer.as_mut().and_then(Arc::get_mut) {
+            Some(instance_buffer) if instances_size <= instance_buffer.size() => {
+                wgpu.queue().write_buffer(
+                    instance_buffer,
+                    0,
+                    bytemuck::cast_slice(&self.instances),
+                );
+            }
+            _ => {
+                self.instance_buffer = Some(Arc::new(wgpu.device().create_buffer_init(
+                    &wgpu::util::BufferInitDescriptor {
+                        label: Some("static_sprite_instance_buffer"),
+                        contents: bytemuck::cast_slice(&self.instances),
+                        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                    },
+                )));
+            }
+        }
+    }
+
+    pub(crate) fn to_draw(&self) -> Option<StaticSpriteBatchDraw> {
+        if self.instances.is_empty() {
+            return None;
+        }
+
+        Some(StaticSpriteBatchDraw {
+            instance_buffer: self.instance_buffer.clone()?,
+            texture: self.texture.clone(),
+            sampler: self.sampler,
+            instances: 0..(self.instances.len() as u32),
+        })
+    }
+}
+
+impl Clone for StaticSpriteBatch {
+    fn clone(&self) -> Self {
+        Self {
+            texture: self.texture.clone(),
+            sampler: self.sampler,
+            instances: self.instances.clone(),
+            instance_buffer: None,
+            dirty: true,
+        }
+    }
+}
+
+impl Drawable for &mut StaticSpriteBatch {
+    fn draw(self, canvas: &mut Canvas) {
+        canvas.draw_static_sprites(self);
+    }
+}
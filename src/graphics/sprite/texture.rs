@@ -1,13 +1,29 @@
 use crate::game::GameResult;
-use crate::graphics::GraphicsContext;
-use anyhow::Context;
+use crate::graphics::{load_image_from_memory, open_image, Bounds, GraphicsContext, WgpuContext};
+use anyhow::ensure;
 use glam::UVec2;
+use image::imageops::FilterType;
+use image::{DynamicImage, RgbaImage};
+use std::borrow::Cow;
 use std::path::Path;
 use std::sync::Arc;
 use wgpu::util::DeviceExt;
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct TextureOptions {
+    pub generate_mipmaps: bool,
+}
+
+impl TextureOptions {
+    pub const fn generate_mipmaps(mut self, generate_mipmaps: bool) -> Self {
+        self.generate_mipmaps = generate_mipmaps;
+        self
+    }
+}
+
 #[derive(Debug)]
 struct TextureData {
+    texture: wgpu::Texture,
     view: wgpu::TextureView,
     bind_group: wgpu::BindGroup,
 }
@@ -16,6 +32,7 @@ struct TextureData {
 pub struct Texture {
     data: Arc<TextureData>,
     size: UVec2,
+    mip_level_count: u32,
 }
 
 impl Texture {
@@ -23,36 +40,139 @@ impl Texture {
     where
         G: AsRef<GraphicsContext>,
         P: AsRef<Path>,
+    {
+        Self::from_file_with_options(graphics, path, TextureOptions::default())
+    }
+
+    pub fn from_file_with_options<G, P>(
+        graphics: G,
+        path: P,
+        options: TextureOptions,
+    ) -> GameResult<Self>
+    where
+        G: AsRef<GraphicsContext>,
+        P: AsRef<Path>,
+    {
+        let image = open_image(path.as_ref())?;
+        Ok(Self::from_image_with_options(graphics, &image, options))
+    }
+
+    pub fn from_bytes<G>(graphics: G, bytes: &[u8]) -> GameResult<Self>
+    where
+        G: AsRef<GraphicsContext>,
+    {
+        Self::from_bytes_with_options(graphics, bytes, TextureOptions::default())
+    }
+
+    pub fn from_bytes_with_options<G>(
+        graphics: G,
+        bytes: &[u8],
+        options: TextureOptions,
+    ) -> GameResult<Self>
+    where
+        G: AsRef<GraphicsContext>,
+    {
+        let image = load_image_from_memory(bytes)?;
+        Ok(Self::from_image_with_options(graphics, &image, options))
+    }
+
+    pub fn from_image<G>(graphics: G, image: &DynamicImage) -> Self
+    where
+        G: AsRef<GraphicsContext>,
+    {
+        Self::from_image_with_options(graphics, image, TextureOptions::default())
+    }
+
+    pub fn from_image_with_options<G>(
+        graphics: G,
+        image: &DynamicImage,
+        options: TextureOptions,
+    ) -> Self
+    where
+        G: AsRef<GraphicsContext>,
+    {
+        let image = image.to_rgba8();
+        let size = UVec2::new(image.width(), image.height());
+        Self::new(graphics.as_ref(), size, &image, options)
+    }
+
+    pub fn from_rgba<G, S>(graphics: G, size: S, pixels: &[u8]) -> GameResult<Self>
+    where
+        G: AsRef<GraphicsContext>,
+        S: Into<UVec2>,
     {
         let graphics = graphics.as_ref();
-        let path = path.as_ref();
+        let size = size.into();
+        let pixels_len = rgba_len(graphics, size)?;
 
-        let image = image::open(path)
-            .map(|image| image.to_rgba8())
-            .with_context(|| format!("Failed to open image file: '{}'", path.display()))?;
+        ensure!(
+            pixels.len() == pixels_len,
+            "Expected {} bytes of RGBA data for a {}x{} texture, got {}",
+            pixels_len,
+            size.x,
+            size.y,
+            pixels.len(),
+        );
 
-        let view = graphics
-            .device()
-            .create_texture_with_data(
-                graphics.queue(),
-                &wgpu::TextureDescriptor {
-                    label: Some("rgba_texture"),
-                    size: wgpu::Extent3d {
-                        width: image.width(),
-                        height: image.height(),
-                        depth_or_array_layers: 1,
-                    },
-                    mip_level_count: 1,
-                    sample_count: 1,
-                    dimension: wgpu::TextureDimension::D2,
-                    format: wgpu::TextureFormat::Rgba8UnormSrgb,
-                    usage: wgpu::TextureUsages::TEXTURE_BINDING,
-                    view_formats: &[],
+        Ok(Self::new(graphics, size, pixels, TextureOptions::default()))
+    }
+
+    pub fn empty<G, S>(graphics: G, size: S) -> GameResult<Self>
+    where
+        G: AsRef<GraphicsContext>,
+        S: Into<UVec2>,
+    {
+        Self::empty_with_options(graphics, size, TextureOptions::default())
+    }
+
+    pub fn empty_with_options<G, S>(
+        graphics: G,
+        size: S,
+        options: TextureOptions,
+    ) -> GameResult<Self>
+    where
+        G: AsRef<GraphicsContext>,
+        S: Into<UVec2>,
+    {
+        let graphics = graphics.as_ref();
+        let size = size.into();
+        let pixels_len = rgba_len(graphics, size)?;
+        Ok(Self::new(graphics, size, &vec![0; pixels_len], options))
+    }
+
+    fn new(
+        graphics: &GraphicsContext,
+        size: UVec2,
+        pixels: &[u8],
+        options: TextureOptions,
+    ) -> Self {
+        let (mip_level_count, data) = if options.generate_mipmaps {
+            generate_mipmaps(size, pixels)
+        } else {
+            (1, Cow::Borrowed(pixels))
+        };
+
+        let texture = graphics.device().create_texture_with_data(
+            graphics.queue(),
+            &wgpu::TextureDescriptor {
+                label: Some("rgba_texture"),
+                size: wgpu::Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
                 },
-                wgpu::util::TextureDataOrder::default(),
-                &image,
-            )
-            .create_view(&Default::default());
+                mip_level_count,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::default(),
+            &data,
+        );
+
+        let view = texture.create_view(&Default::default());
 
         let bind_group = graphics
             .device()
@@ -65,10 +185,89 @@ impl Texture {
                 }],
             });
 
-        Ok(Self {
-            data: Arc::new(TextureData { view, bind_group }),
-            size: UVec2::new(image.width(), image.height()),
-        })
+        Self {
+            data: Arc::new(TextureData {
+                texture,
+                view,
+                bind_group,
+            }),
+            size,
+            mip_level_count,
+        }
+    }
+
+    pub fn write_region<W, B>(&self, wgpu: W, bounds: B, pixels: &[u8]) -> GameResult<()>
+    where
+        W: AsRef<WgpuContext>,
+        B: Into<Bounds>,
+    {
+        ensure!(
+            self.mip_level_count == 1,
+            "Cannot write to a texture with mipmaps",
+        );
+
+        let bounds = bounds.into();
+        let position = bounds.position().as_uvec2();
+        let size = bounds.size().as_uvec2();
+
+        ensure!(
+            bounds.x >= 0.0
+                && bounds.y >= 0.0
+                && position.x as u64 + size.x as u64 <= self.size.x as u64
+                && position.y as u64 + size.y as u64 <= self.size.y as u64,
+            "Region {}x{} at ({}, {}) is out of the texture's {}x{} bounds",
+            size.x,
+            size.y,
+            bounds.x,
+            bounds.y,
+            self.size.x,
+            self.size.y,
+        );
+
+        let pixels_len = size.x as usize * size.y as usize * 4;
+
+        ensure!(
+            pixels.len() == pixels_len,
+            "Expected {} bytes of RGBA data for a {}x{} region, got {}",
+            pixels_len,
+            size.x,
+            size.y,
+            pixels.len(),
+        );
+
+        wgpu.as_ref().queue().write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &self.data.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: position.x,
+                    y: position.y,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            pixels,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(size.x * 4),
+                rows_per_image: Some(size.y),
+            },
+            wgpu::Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn write<W>(&self, wgpu: W, pixels: &[u8]) -> GameResult<()>
+    where
+        W: AsRef<WgpuContext>,
+    {
+        let size = self.size.as_vec2();
+        self.write_region(wgpu, Bounds::new(0.0, 0.0, size.x, size.y), pixels)
     }
 
     pub fn view(&self) -> &wgpu::TextureView {
@@ -82,6 +281,10 @@ impl Texture {
     pub fn size(&self) -> UVec2 {
         self.size
     }
+
+    pub fn mip_level_count(&self) -> u32 {
+        self.mip_level_count
+    }
 }
 
 impl PartialEq for Texture {
@@ -93,3 +296,40 @@ impl PartialEq for Texture {
 impl Eq for Texture {
     // Empty
 }
+
+fn rgba_len(graphics: &GraphicsContext, size: UVec2) -> GameResult<usize> {
+    let max_size = graphics.device().limits().max_texture_dimension_2d;
+
+    ensure!(
+        size.x > 0 && size.y > 0,
+        "Texture size must not be zero, got {}x{}",
+        size.x,
+        size.y,
+    );
+
+    ensure!(
+        size.x <= max_size && size.y <= max_size,
+        "Texture size {}x{} exceeds the maximum size of {}x{}",
+        size.x,
+        size.y,
+        max_size,
+        max_size,
+    );
+
+    Ok(size.x as usize * size.y as usize * 4)
+}
+
+fn generate_mipmaps(size: UVec2, pixels: &[u8]) -> (u32, Cow<'_, [u8]>) {
+    let mip_level_count = u32::BITS - size.max_element().max(1).leading_zeros();
+    let mut image = RgbaImage::from_raw(size.x, size.y, pixels.to_vec()).unwrap();
+    let mut data = pixels.to_vec();
+
+    for _ in 1..mip_level_count {
+        let width = (image.width() / 2).max(1);
+        let height = (image.height() / 2).max(1);
+        image = image::imageops::resize(&image, width, height, FilterType::Triangle);
+        data.extend_from_slice(&image);
+    }
+
+    (mip_level_count, Cow::Owned(data))
+}
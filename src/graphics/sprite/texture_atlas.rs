@@ -0,0 +1,321 @@
+use crate::game::GameResult;
+use crate::graphics::sprite::{Sprite, Texture, TextureOptions};
+use crate::graphics::{load_image_from_memory, open_image, Bounds, GraphicsContext};
+use anyhow::{bail, ensure};
+use glam::UVec2;
+use image::{DynamicImage, GenericImage, RgbaImage};
+use std::path::Path;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct AtlasRegionId(usize);
+
+#[derive(Clone, Copy, Debug)]
+pub struct AtlasRegion {
+    pub page: usize,
+    pub bounds: Bounds,
+}
+
+#[derive(Clone, Debug)]
+pub struct TextureAtlasBuilder {
+    page_size: UVec2,
+    padding: u32,
+    extrusion: u32,
+    texture_options: TextureOptions,
+    images: Vec<RgbaImage>,
+}
+
+impl TextureAtlasBuilder {
+    pub fn new<S>(page_size: S) -> Self
+    where
+        S: Into<UVec2>,
+    {
+        Self {
+            page_size: page_size.into(),
+            padding: 1,
+            extrusion: 0,
+            texture_options: TextureOptions::default(),
+            images: Vec::new(),
+        }
+    }
+
+    pub fn padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn extrusion(mut self, extrusion: u32) -> Self {
+        self.extrusion = extrusion;
+        self
+    }
+
+    pub fn texture_options(mut self, texture_options: TextureOptions) -> Self {
+        self.texture_options = texture_options;
+        self
+    }
+
+    pub fn add_image(&mut self, image: &DynamicImage) -> AtlasRegionId {
+        self.images.push(image.to_rgba8());
+        AtlasRegionId(self.images.len() - 1)
+    }
+
+    pub fn add_file<P>(&mut self, path: P) -> GameResult<AtlasRegionId>
+    where
+        P: AsRef<Path>,
+    {
+        let image = open_image(path.as_ref())?;
+        Ok(self.add_image(&image))
+    }
+
+    pub fn add_bytes(&mut self, bytes: &[u8]) -> GameResult<AtlasRegionId> {
+        let image = load_image_from_memory(bytes)?;
+        Ok(self.add_image(&image))
+    }
+
+    pub fn build<G>(self, graphics: G) -> GameResult<TextureAtlas>
+    where
+        G: AsRef<GraphicsContext>,
+    {
+        let graphics = graphics.as_ref();
+
+        let mut order = (0..self.images.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| std::cmp::Reverse(self.images[i].height()));
+
+        let mut packers = Vec::<ShelfPacker>::new();
+        let mut pages = Vec::<RgbaImage>::new();
+        let mut regions = vec![None; self.images.len()];
+
+        for i in order {
+            let block = extrude(&self.images[i], self.extrusion);
+            let slot_size = UVec2::new(block.width(), block.height()) + self.padding;
+
+            let (page, position) = match pack(&mut packers, slot_size) {
+                Some(location) => location,
+                None => {
+                    let mut packer = ShelfPacker::new(self.page_size);
+
+                    let Some(position) = packer.pack(slot_size) else {
+                        bail!(
+                            "Image of size {}x{} does not fit in atlas page of size {}x{}",
+                            self.images[i].width(),
+                            self.images[i].height(),
+                            self.page_size.x,
+                            self.page_size.y,
+                        );
+                    };
+
+                    packers.push(packer);
+                    pages.push(RgbaImage::new(self.page_size.x, self.page_size.y));
+                    (packers.len() - 1, position)
+                }
+            };
+
+            pages[page].copy_from(&block, position.x, position.y)?;
+            regions[i] = Some(region(page, position, &self.images[i], self.extrusion));
+        }
+
+        let pages = pages
+            .into_iter()
+            .map(|page| {
+                Texture::from_image_with_options(
+                    graphics,
+                    &DynamicImage::ImageRgba8(page),
+                    self.texture_options,
+                )
+            })
+            .collect();
+
+        Ok(TextureAtlas {
+            page_size: self.page_size,
+            padding: self.padding,
+            extrusion: self.extrusion,
+            texture_options: self.texture_options,
+            pages,
+            packers,
+            regions: regions.into_iter().map(Option::unwrap).collect(),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TextureAtlas {
+    page_size: UVec2,
+    padding: u32,
+    extrusion: u32,
+    texture_options: TextureOptions,
+    pages: Vec<Texture>,
+    packers: Vec<ShelfPacker>,
+    regions: Vec<AtlasRegion>,
+}
+
+impl TextureAtlas {
+    pub fn builder<S>(page_size: S) -> TextureAtlasBuilder
+    where
+        S: Into<UVec2>,
+    {
+        TextureAtlasBuilder::new(page_size)
+    }
+
+    pub fn insert<G>(&mut self, graphics: G, image: &DynamicImage) -> GameResult<AtlasRegionId>
+    where
+        G: AsRef<GraphicsContext>,
+    {
+        ensure!(
+            !self.texture_options.generate_mipmaps,
+            "Cannot insert images into a texture atlas with mipmaps",
+        );
+
+        let graphics = graphics.as_ref();
+        let image = image.to_rgba8();
+        let block = extrude(&image, self.extrusion);
+        let slot_size = UVec2::new(block.width(), block.height()) + self.padding;
+
+        let (page, position) = match pack(&mut self.packers, slot_size) {
+            Some(location) => location,
+            None => {
+                let mut packer = ShelfPacker::new(self.page_size);
+
+                let Some(position) = packer.pack(slot_size) else {
+                    bail!(
+                        "Image of size {}x{} does not fit in atlas page of size {}x{}",
+                        image.width(),
+                        image.height(),
+                        self.page_size.x,
+                        self.page_size.y,
+                    );
+                };
+
+                self.packers.push(packer);
+                self.pages.push(Texture::empty_with_options(
+                    graphics,
+                    self.page_size,
+                    self.texture_options,
+                )?);
+                (self.packers.len() - 1, position)
+            }
+        };
+
+        let block_bounds = Bounds::new(
+            position.x as f32,
+            position.y as f32,
+            block.width() as f32,
+            block.height() as f32,
+        );
+
+        self.pages[page].write_region(graphics, block_bounds, &block)?;
+        self.regions.push(region(page, position, &image, self.extrusion));
+
+        Ok(AtlasRegionId(self.regions.len() - 1))
+    }
+
+    pub fn region(&self, id: AtlasRegionId) -> AtlasRegion {
+        self.regions[id.0]
+    }
+
+    pub fn regions(&self) -> &[AtlasRegion] {
+        &self.regions
+    }
+
+    pub fn page(&self, index: usize) -> &Texture {
+        &self.pages[index]
+    }
+
+    pub fn pages(&self) -> &[Texture] {
+        &self.pages
+    }
+
+    pub fn sprite(&self, id: AtlasRegionId) -> Sprite<'_> {
+        let region = self.region(id);
+        Sprite::new(&self.pages[region.page]).uv_bounds(region.bounds)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Shelf {
+    y: u32,
+    height: u32,
+    used_width: u32,
+}
+
+#[derive(Clone, Debug)]
+struct ShelfPacker {
+    size: UVec2,
+    shelves: Vec<Shelf>,
+}
+
+impl ShelfPacker {
+    fn new(size: UVec2) -> Self {
+        Self {
+            size,
+            shelves: Vec::new(),
+        }
+    }
+
+    fn pack(&mut self, size: UVec2) -> Option<UVec2> {
+        let best_shelf = self
+            .shelves
+            .iter_mut()
+            .filter(|shelf| shelf.height >= size.y && self.size.x - shelf.used_width >= size.x)
+            .min_by_key(|shelf| shelf.height - size.y);
+
+        if let Some(shelf) = best_shelf {
+            let position = UVec2::new(shelf.used_width, shelf.y);
+            shelf.used_width += size.x;
+            return Some(position);
+        }
+
+        let y = self
+            .shelves
+            .last()
+            .map_or(0, |shelf| shelf.y + shelf.height);
+
+        if size.x > self.size.x || y + size.y > self.size.y {
+            return None;
+        }
+
+        self.shelves.push(Shelf {
+            y,
+            height: size.y,
+            used_width: size.x,
+        });
+
+        Some(UVec2::new(0, y))
+    }
+}
+
+fn pack(packers: &mut [ShelfPacker], size: UVec2) -> Option<(usize, UVec2)> {
+    packers
+        .iter_mut()
+        .enumerate()
+        .find_map(|(page, packer)| packer.pack(size).map(|position| (page, position)))
+}
+
+fn region(page: usize, position: UVec2, image: &RgbaImage, extrusion: u32) -> AtlasRegion {
+    AtlasRegion {
+        page,
+        bounds: Bounds::new(
+            (position.x + extrusion) as f32,
+            (position.y + extrusion) as f32,
+            image.width() as f32,
+            image.height() as f32,
+        ),
+    }
+}
+
+fn extrude(image: &RgbaImage, extrusion: u32) -> RgbaImage {
+    if extrusion == 0 || image.width() == 0 || image.height() == 0 {
+        return image.clone();
+    }
+
+    let max_x = image.width() - 1;
+    let max_y = image.height() - 1;
+
+    RgbaImage::from_fn(
+        image.width() + extrusion * 2,
+        image.height() + extrusion * 2,
+        |x, y| {
+            let x = x.saturating_sub(extrusion).min(max_x);
+            let y = y.saturating_sub(extrusion).min(max_y);
+            *image.get_pixel(x, y)
+        },
+    )
+}
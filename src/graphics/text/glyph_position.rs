@@ -0,0 +1,8 @@
+use crate::graphics::Bounds;
+
+#[derive(Clone, Copy, Debug)]
+pub struct GlyphPosition {
+    pub bounds: Bounds,
+    pub section_index: usize,
+    pub byte_offset: usize,
+}
@@ -1,18 +1,24 @@
 mod font;
 mod glyph_data;
+mod glyph_position;
 mod glyph_texture;
 mod text;
 mod text_instance;
 
 pub use self::font::*;
 pub use self::glyph_data::*;
+pub use self::glyph_position::*;
 pub use self::glyph_texture::*;
 pub use self::text::*;
 pub use self::text_instance::*;
 
-use crate::graphics::{vertex_attr_array, SharedBindGroupLayouts, WgpuContext};
+use crate::graphics::{vertex_attr_array, Bounds, SharedBindGroupLayouts, WgpuContext};
 use glam::Vec2;
-use glyph_brush::{BrushAction, BrushError, FontId, GlyphBrush, GlyphBrushBuilder};
+use glyph_brush::ab_glyph::Font as _;
+use glyph_brush::{
+    BrushAction, BrushError, BuiltInLineBreaker, FontId, GlyphBrush, GlyphBrushBuilder,
+    GlyphCruncher,
+};
 use rustc_hash::FxHashMap;
 use std::mem;
 use std::ops::Range;
@@ -149,63 +155,53 @@ impl TextRenderer {
     }
 
     pub fn add(&mut self, text: Text) -> u32 {
-        let (layout, anchor_offset) = {
-            let (h_align, h_align_scale) = match text.h_align {
-                HorizontalAlign::Left => (glyph_brush::HorizontalAlign::Left, 0.0),
-                HorizontalAlign::Center => (glyph_brush::HorizontalAlign::Center, 0.5),
-                HorizontalAlign::Right => (glyph_brush::HorizontalAlign::Right, 1.0),
-            };
+        let text_index = self.text_index;
+        let glyph_brush_section = self.section(&text, text_index);
 
-            let (v_align, v_align_scale) = match text.v_align {
-                VerticalAlign::Top => (glyph_brush::VerticalAlign::Top, 0.0),
-                VerticalAlign::Center => (glyph_brush::VerticalAlign::Center, 0.5),
-                VerticalAlign::Bottom => (glyph_brush::VerticalAlign::Bottom, 1.0),
-            };
+        self.text_index += 1;
+        self.glyph_brush.queue(glyph_brush_section);
+        text_index
+    }
 
-            let layout = glyph_brush::Layout::Wrap {
-                line_breaker: glyph_brush::BuiltInLineBreaker::UnicodeLineBreaker,
-                h_align,
-                v_align,
-            };
+    pub fn measure_text(&mut self, text: &Text) -> Vec2 {
+        let glyph_brush_section = self.section(text, self.text_index);
 
-            let anchor_offset =
-                -Vec2::new(h_align_scale, v_align_scale) * text.bounds + text.anchor_offset;
+        self.glyph_brush
+            .glyph_bounds(glyph_brush_section)
+            .map_or(Vec2::ZERO, |rect| Vec2::new(rect.width(), rect.height()))
+    }
 
-            (layout, anchor_offset)
-        };
+    // Bounds are in the text's local space, before its transform is applied.
+    pub fn glyph_positions(&mut self, text: &Text) -> Vec<GlyphPosition> {
+        let (_, align_offset) = Self::layout(text);
+        let offset = align_offset - text.anchor_offset;
+        let glyph_brush_section = self.section(text, self.text_index);
 
-        let text_index = self.text_index;
-        let affine2 = text.transform.to_affine2();
+        let section_glyphs = self
+            .glyph_brush
+            .glyphs(glyph_brush_section)
+            .cloned()
+            .collect::<Vec<_>>();
 
-        let glyph_brush_texts = text
-            .sections
-            .iter()
-            .map(|section| {
-                glyph_brush::Text {
-                    text: section.content,
-                    scale: section.font_size.unwrap_or(text.font_size).into(),
-                    font_id: self.get_or_insert_font(section.font.unwrap_or(text.font)),
-                    extra: GlyphData {
-                        text_index,
-                        affine2,
-                        anchor_offset,
-                        linear_color: section.color.unwrap_or(text.color).to_linear_vec4(),
-                    },
+        let fonts = self.glyph_brush.fonts();
+
+        section_glyphs
+            .into_iter()
+            .map(|section_glyph| {
+                let rect = fonts[section_glyph.font_id.0].glyph_bounds(&section_glyph.glyph);
+
+                GlyphPosition {
+                    bounds: Bounds::new(
+                        rect.min.x + offset.x,
+                        rect.min.y + offset.y,
+                        rect.width(),
+                        rect.height(),
+                    ),
+                    section_index: section_glyph.section_index,
+                    byte_offset: section_glyph.byte_index,
                 }
             })
-            .collect::<Vec<_>>();
-
-        self.text_index += 1;
-
-        let glyph_brush_section = glyph_brush::Section {
-            screen_position: (0.0, 0.0),
-            bounds: text.bounds.into(),
-            layout,
-            text: glyph_brush_texts,
-        };
-
-        self.glyph_brush.queue(glyph_brush_section);
-        text_index
+            .collect()
     }
 
     pub fn end(&mut self, wgpu: &WgpuContext) {
@@ -301,6 +297,64 @@ impl TextRenderer {
         pass.draw(0..4, start_instance..end_instance);
     }
 
+    fn layout(text: &Text) -> (glyph_brush::Layout<BuiltInLineBreaker>, Vec2) {
+        let (h_align, h_align_scale) = match text.h_align {
+            HorizontalAlign::Left => (glyph_brush::HorizontalAlign::Left, 0.0),
+            HorizontalAlign::Center => (glyph_brush::HorizontalAlign::Center, 0.5),
+            HorizontalAlign::Right => (glyph_brush::HorizontalAlign::Right, 1.0),
+        };
+
+        let (v_align, v_align_scale) = match text.v_align {
+            VerticalAlign::Top => (glyph_brush::VerticalAlign::Top, 0.0),
+            VerticalAlign::Center => (glyph_brush::VerticalAlign::Center, 0.5),
+            VerticalAlign::Bottom => (glyph_brush::VerticalAlign::Bottom, 1.0),
+        };
+
+        let layout = glyph_brush::Layout::Wrap {
+            line_breaker: BuiltInLineBreaker::UnicodeLineBreaker,
+            h_align,
+            v_align,
+        };
+
+        let align_offset = Vec2::new(h_align_scale, v_align_scale) * text.bounds;
+        (layout, align_offset)
+    }
+
+    fn section<'a>(
+        &mut self,
+        text: &Text<'a>,
+        text_index: u32,
+    ) -> glyph_brush::Section<'a, GlyphData> {
+        let (layout, align_offset) = Self::layout(text);
+        let anchor_offset = text.anchor_offset - align_offset;
+        let affine2 = text.transform.to_affine2();
+
+        let glyph_brush_texts = text
+            .sections
+            .iter()
+            .map(|section| {
+                glyph_brush::Text {
+                    text: section.content,
+                    scale: section.font_size.unwrap_or(text.font_size).into(),
+                    font_id: self.get_or_insert_font(section.font.unwrap_or(text.font)),
+                    extra: GlyphData {
+                        text_index,
+                        affine2,
+                        anchor_offset,
+                        linear_color: section.color.unwrap_or(text.color).to_linear_vec4(),
+                    },
+                }
+            })
+            .collect::<Vec<_>>();
+
+        glyph_brush::Section {
+            screen_position: (0.0, 0.0),
+            bounds: text.bounds.into(),
+            layout,
+            text: glyph_brush_texts,
+        }
+    }
+
     fn get_or_insert_font(&mut self, font: &Font) -> FontId {
         *self
             .fonts
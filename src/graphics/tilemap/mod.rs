@@ -0,0 +1,18 @@
+mod properties;
+mod tile_data;
+mod tilemap;
+mod tilemap_layer;
+mod tilemap_view;
+mod tileset;
+mod tmj;
+mod tmx;
+
+pub use self::properties::*;
+pub use self::tilemap::*;
+pub use self::tilemap_layer::*;
+pub use self::tilemap_view::*;
+pub use self::tileset::*;
+
+pub(crate) use self::tile_data::*;
+pub(crate) use self::tmj::*;
+pub(crate) use self::tmx::*;
@@ -0,0 +1,58 @@
+use rustc_hash::FxHashMap;
+
+pub type TiledProperties = FxHashMap<String, TiledProperty>;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TiledProperty {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Color(String),
+    File(String),
+    Object(u32),
+}
+
+impl TiledProperty {
+    pub(crate) fn parse(property_type: &str, value: &str) -> Self {
+        match property_type {
+            "bool" => Self::Bool(value == "true"),
+            "int" => Self::Int(value.parse().unwrap_or_default()),
+            "float" => Self::Float(value.parse().unwrap_or_default()),
+            "color" => Self::Color(value.to_string()),
+            "file" => Self::File(value.to_string()),
+            "object" => Self::Object(value.parse().unwrap_or_default()),
+            _ => Self::String(value.to_string()),
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Self::Int(value) => Some(*value),
+            Self::Object(value) => Some(*value as i64),
+            _ => None,
+        }
+    }
+
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Float(value) => Some(*value),
+            Self::Int(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) | Self::Color(value) | Self::File(value) => Some(value),
+            _ => None,
+        }
+    }
+}
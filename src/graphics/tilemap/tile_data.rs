@@ -0,0 +1,146 @@
+use crate::game::GameResult;
+use crate::graphics::sprite::Texture;
+use crate::graphics::tilemap::{Tile, Tileset};
+use crate::graphics::GraphicsContext;
+use anyhow::{anyhow, bail, ensure};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use glam::UVec2;
+use std::io::Read;
+use std::path::Path;
+
+pub(crate) fn decode_tile_data(
+    data: &str,
+    encoding: Option<&str>,
+    compression: Option<&str>,
+) -> GameResult<Vec<u32>> {
+    match encoding {
+        None | Some("csv") => {
+            data.split(',')
+                .map(str::trim)
+                .filter(|gid| !gid.is_empty())
+                .map(|gid| {
+                    gid.parse::<u32>()
+                        .map_err(|_| anyhow!("Invalid tile data: '{gid}'"))
+                })
+                .collect()
+        }
+        Some("base64") => {
+            let bytes = decode_base64(data)?;
+
+            let bytes = match compression {
+                None | Some("") => bytes,
+                Some("zlib") => decompress(ZlibDecoder::new(bytes.as_slice()))?,
+                Some("gzip") => decompress(GzDecoder::new(bytes.as_slice()))?,
+                Some(compression) => bail!("Unsupported tile data compression: '{compression}'"),
+            };
+
+            ensure!(bytes.len() % 4 == 0, "Invalid tile data length");
+
+            Ok(bytes
+                .chunks_exact(4)
+                .map(|gid| u32::from_le_bytes(gid.try_into().unwrap()))
+                .collect())
+        }
+        Some(encoding) => bail!("Unsupported tile data encoding: '{encoding}'"),
+    }
+}
+
+pub(crate) fn gids_to_tiles(gids: &[u32], size: UVec2) -> GameResult<Vec<Option<Tile>>> {
+    let tile_count = size
+        .x
+        .checked_mul(size.y)
+        .ok_or_else(|| anyhow!("Tile layer is too large: {}x{}", size.x, size.y))?;
+
+    ensure!(
+        gids.len() == tile_count as usize,
+        "Expected {} tiles for a {}x{} layer, got {}",
+        tile_count,
+        size.x,
+        size.y,
+        gids.len(),
+    );
+
+    Ok(gids.iter().copied().map(Tile::from_raw_gid).collect())
+}
+
+pub(crate) fn load_tileset_texture(
+    graphics: &GraphicsContext,
+    directory: &Path,
+    image: Option<&str>,
+) -> GameResult<Texture> {
+    let Some(image) = image else {
+        bail!("Image collection tilesets are not supported");
+    };
+
+    Texture::from_file(graphics, directory.join(image))
+}
+
+pub(crate) fn complete_tileset_grid(tileset: &mut Tileset) -> GameResult<()> {
+    let texture_size = tileset.texture.size();
+    let stride = tileset.tile_size.saturating_add(UVec2::splat(tileset.spacing));
+
+    let margin = tileset
+        .margin
+        .checked_mul(2)
+        .ok_or_else(|| anyhow!("Invalid tileset margin: {}", tileset.margin))?;
+
+    let available = texture_size
+        .saturating_sub(UVec2::splat(margin))
+        .saturating_add(UVec2::splat(tileset.spacing));
+
+    let cells = available / stride.max(UVec2::ONE);
+
+    if tileset.columns == 0 {
+        tileset.columns = cells.x;
+    }
+
+    if tileset.tile_count == 0 {
+        tileset.tile_count = tileset
+            .columns
+            .checked_mul(cells.y)
+            .ok_or_else(|| anyhow!("Too many tiles in tileset '{}'", tileset.name))?;
+    }
+
+    Ok(())
+}
+
+fn decompress<R>(mut reader: R) -> GameResult<Vec<u8>>
+where
+    R: Read,
+{
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .map_err(|error| anyhow!("Failed to decompress tile data: {error}"))?;
+
+    Ok(bytes)
+}
+
+fn decode_base64(data: &str) -> GameResult<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(data.len() * 3 / 4);
+    let mut buffer = 0_u32;
+    let mut bits = 0;
+
+    for c in data.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            c if c.is_ascii_whitespace() => continue,
+            _ => bail!("Invalid base64 tile data"),
+        };
+
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+
+    Ok(bytes)
+}
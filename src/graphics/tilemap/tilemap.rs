@@ -0,0 +1,373 @@
+use crate::game::GameResult;
+use crate::graphics::sprite::{SpriteInstance, StaticSpriteBatch};
+use crate::graphics::tilemap::{
+    load_tmj, load_tmx, ObjectLayer, Tile, TileLayer, TiledObject, TiledProperties, TilemapLayer,
+    TilemapView, Tileset,
+};
+use crate::graphics::{Bounds, GraphicsContext};
+use anyhow::{anyhow, bail, ensure};
+use glam::{UVec2, Vec2, Vec4};
+use std::path::Path;
+
+const CHUNK_SIZE: u32 = 16;
+
+#[derive(Clone, Debug)]
+pub(crate) struct AnimatedChunkTile {
+    pub tileset: usize,
+    pub tile_id: u32,
+    pub frame_tile_id: u32,
+    pub tile: Tile,
+    pub batch: usize,
+    pub index: usize,
+}
+
+#[derive(Clone, Default, Debug)]
+pub(crate) struct TileChunk {
+    pub bounds: Bounds,
+    pub batches: Vec<StaticSpriteBatch>,
+    pub animated_tiles: Vec<AnimatedChunkTile>,
+    pub linear_color: Vec4,
+}
+
+impl TileChunk {
+    pub fn update(&mut self, tilesets: &[Tileset], time: f32, linear_color: Vec4) {
+        for animated_tile in self.animated_tiles.iter_mut() {
+            let tileset = &tilesets[animated_tile.tileset];
+            let frame_tile_id = tileset.animated_tile_id(animated_tile.tile_id, time);
+
+            if frame_tile_id == animated_tile.frame_tile_id {
+                continue;
+            }
+
+            animated_tile.frame_tile_id = frame_tile_id;
+
+            let batch = &mut self.batches[animated_tile.batch];
+            let mut instance = batch.instances()[animated_tile.index];
+            instance.uv_edges = tile_uv_edges(tileset, frame_tile_id, animated_tile.tile);
+            batch.set(animated_tile.index, instance);
+        }
+
+        if linear_color == self.linear_color {
+            return;
+        }
+
+        self.linear_color = linear_color;
+
+        for batch in self.batches.iter_mut() {
+            for index in 0..batch.len() {
+                let mut instance = batch.instances()[index];
+                instance.linear_color = linear_color;
+                batch.set(index, instance);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Tilemap {
+    size: UVec2,
+    tile_size: UVec2,
+    tilesets: Vec<Tileset>,
+    layers: Vec<TilemapLayer>,
+    properties: TiledProperties,
+    chunks: Vec<Vec<TileChunk>>,
+}
+
+impl Tilemap {
+    pub fn new(
+        size: UVec2,
+        tile_size: UVec2,
+        tilesets: Vec<Tileset>,
+        layers: Vec<TilemapLayer>,
+        properties: TiledProperties,
+    ) -> Self {
+        let mut tilemap = Self {
+            size,
+            tile_size,
+            tilesets,
+            layers,
+            properties,
+            chunks: Vec::new(),
+        };
+
+        tilemap.chunks = (0..tilemap.layers.len())
+            .map(|layer| tilemap.build_layer_chunks(layer))
+            .collect();
+
+        tilemap
+    }
+
+    pub fn from_file<G, P>(graphics: G, path: P) -> GameResult<Self>
+    where
+        G: AsRef<GraphicsContext>,
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("tmx") => load_tmx(graphics.as_ref(), path),
+            Some("tmj" | "json") => load_tmj(graphics.as_ref(), path),
+            _ => bail!("Unsupported tilemap format: '{}'", path.display()),
+        }
+    }
+
+    pub fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    pub fn tile_size(&self) -> UVec2 {
+        self.tile_size
+    }
+
+    pub fn pixel_size(&self) -> Vec2 {
+        (self.size * self.tile_size).as_vec2()
+    }
+
+    pub fn tilesets(&self) -> &[Tileset] {
+        &self.tilesets
+    }
+
+    pub fn tileset_for_gid(&self, gid: u32) -> Option<(usize, &Tileset)> {
+        self.tilesets
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, tileset)| tileset.first_gid <= gid)
+    }
+
+    pub fn properties(&self) -> &TiledProperties {
+        &self.properties
+    }
+
+    pub fn layers(&self) -> &[TilemapLayer] {
+        &self.layers
+    }
+
+    pub fn layer(&self, index: usize) -> Option<&TilemapLayer> {
+        self.layers.get(index)
+    }
+
+    pub fn layer_index(&self, name: &str) -> Option<usize> {
+        self.layers.iter().position(|layer| layer.name() == name)
+    }
+
+    pub fn tile_layer(&self, name: &str) -> Option<&TileLayer> {
+        self.layers
+            .iter()
+            .filter_map(TilemapLayer::as_tile_layer)
+            .find(|layer| layer.name == name)
+    }
+
+    pub fn object_layer(&self, name: &str) -> Option<&ObjectLayer> {
+        self.layers
+            .iter()
+            .filter_map(TilemapLayer::as_object_layer)
+            .find(|layer| layer.name == name)
+    }
+
+    pub fn objects(&self) -> impl Iterator<Item = &TiledObject> {
+        self.layers
+            .iter()
+            .filter_map(TilemapLayer::as_object_layer)
+            .flat_map(|layer| layer.objects.iter())
+    }
+
+    pub fn object(&self, name: &str) -> Option<&TiledObject> {
+        self.objects().find(|object| object.name == name)
+    }
+
+    pub fn object_by_id(&self, id: u32) -> Option<&TiledObject> {
+        self.objects().find(|object| object.id == id)
+    }
+
+    pub fn objects_with_class<'a>(
+        &'a self,
+        class: &'a str,
+    ) -> impl Iterator<Item = &'a TiledObject> + 'a {
+        self.objects().filter(move |object| object.class == class)
+    }
+
+    pub fn objects_in(&self, bounds: Bounds) -> impl Iterator<Item = &TiledObject> {
+        self.objects().filter(move |object| {
+            let object_bounds = object.bounds();
+
+            if object_bounds.w == 0.0 || object_bounds.h == 0.0 {
+                bounds.contains(object_bounds.position())
+            } else {
+                object_bounds.intersects(&bounds)
+            }
+        })
+    }
+
+    pub fn tile(&self, layer: usize, x: u32, y: u32) -> Option<Tile> {
+        self.layers.get(layer)?.as_tile_layer()?.tile(x, y)
+    }
+
+    pub fn set_tile(&mut self, layer: usize, x: u32, y: u32, tile: Option<Tile>) -> GameResult<()> {
+        let Some(TilemapLayer::Tiles(tile_layer)) = self.layers.get_mut(layer) else {
+            bail!("Layer {layer} is not a tile layer");
+        };
+
+        ensure!(
+            x < tile_layer.size.x && y < tile_layer.size.y,
+            "Tile position ({x}, {y}) is out of the layer's bounds",
+        );
+
+        tile_layer.tiles[(y * tile_layer.size.x + x) as usize] = tile;
+
+        let chunk_columns = tile_layer.size.x.div_ceil(CHUNK_SIZE);
+        let chunk = UVec2::new(x, y) / CHUNK_SIZE;
+        let chunk_index = (chunk.y * chunk_columns + chunk.x) as usize;
+        let chunk = self.build_chunk(layer, chunk);
+
+        let chunk_slot = self
+            .chunks
+            .get_mut(layer)
+            .and_then(|chunks| chunks.get_mut(chunk_index))
+            .ok_or_else(|| anyhow!("Missing chunk for tile ({x}, {y}) in layer {layer}"))?;
+
+        *chunk_slot = chunk;
+        Ok(())
+    }
+
+    pub fn view(&mut self) -> TilemapView<'_> {
+        TilemapView::new(self)
+    }
+
+    pub(crate) fn layer_chunks_mut(&mut self, layer: usize) -> (&[Tileset], &mut [TileChunk]) {
+        (&self.tilesets, &mut self.chunks[layer])
+    }
+
+    fn build_layer_chunks(&self, layer: usize) -> Vec<TileChunk> {
+        let Some(tile_layer) = self.layers[layer].as_tile_layer() else {
+            return Vec::new();
+        };
+
+        let columns = tile_layer.size.x.div_ceil(CHUNK_SIZE);
+        let rows = tile_layer.size.y.div_ceil(CHUNK_SIZE);
+
+        (0..rows)
+            .flat_map(|y| (0..columns).map(move |x| UVec2::new(x, y)))
+            .map(|chunk| self.build_chunk(layer, chunk))
+            .collect()
+    }
+
+    fn build_chunk(&self, layer: usize, chunk: UVec2) -> TileChunk {
+        let tile_layer = self.layers[layer].as_tile_layer().unwrap();
+        let start = chunk * CHUNK_SIZE;
+        let end = (start + CHUNK_SIZE).min(tile_layer.size);
+
+        let mut min = Vec2::splat(f32::INFINITY);
+        let mut max = Vec2::splat(f32::NEG_INFINITY);
+        let mut batch_tilesets = Vec::<usize>::new();
+        let mut batches = Vec::<StaticSpriteBatch>::new();
+        let mut animated_tiles = Vec::new();
+
+        for y in start.y..end.y {
+            for x in start.x..end.x {
+                let Some(tile) = tile_layer.tile(x, y) else {
+                    continue;
+                };
+
+                let Some((tileset_index, tileset)) = self.tileset_for_gid(tile.gid) else {
+                    continue;
+                };
+
+                let tile_id = tile.gid - tileset.first_gid;
+                let source_size = tileset.tile_size.as_vec2();
+
+                let box_size = if tile.flip_diagonal {
+                    Vec2::new(source_size.y, source_size.x)
+                } else {
+                    source_size
+                };
+
+                let cell = UVec2::new(x, y).as_vec2();
+                let tile_size = self.tile_size.as_vec2();
+
+                let position = Vec2::new(cell.x * tile_size.x, (cell.y + 1.0) * tile_size.y)
+                    - Vec2::new(0.0, box_size.y)
+                    + tileset.offset
+                    + tile_layer.offset;
+
+                let (x_axis, y_axis) = if tile.flip_diagonal {
+                    (Vec2::Y, Vec2::X)
+                } else {
+                    (Vec2::X, Vec2::Y)
+                };
+
+                min = min.min(position);
+                max = max.max(position + box_size);
+
+                let batch = match batch_tilesets.iter().position(|&i| i == tileset_index) {
+                    Some(batch) => batch,
+                    None => {
+                        batch_tilesets.push(tileset_index);
+                        batches.push(StaticSpriteBatch::new(tileset.texture.clone()));
+                        batches.len() - 1
+                    }
+                };
+
+                let index = batches[batch].push(SpriteInstance {
+                    size: source_size,
+                    scale_rotation_x_axis: x_axis,
+                    scale_rotation_y_axis: y_axis,
+                    translation: position,
+                    anchor_offset: Vec2::ZERO,
+                    texture_size: tileset.texture.size().as_vec2(),
+                    uv_edges: tile_uv_edges(tileset, tile_id, tile),
+                    linear_color: Vec4::ONE,
+                    ..Default::default()
+                });
+
+                if tileset.animations.contains_key(&tile_id) {
+                    animated_tiles.push(AnimatedChunkTile {
+                        tileset: tileset_index,
+                        tile_id,
+                        frame_tile_id: tile_id,
+                        tile,
+                        batch,
+                        index,
+                    });
+                }
+            }
+        }
+
+        let bounds = if batches.is_empty() {
+            Bounds::default()
+        } else {
+            Bounds::from_min_max(min, max)
+        };
+
+        TileChunk {
+            bounds,
+            batches,
+            animated_tiles,
+            linear_color: Vec4::ONE,
+        }
+    }
+}
+
+pub(crate) fn tile_uv_edges(tileset: &Tileset, tile_id: u32, tile: Tile) -> Vec4 {
+    let uv_bounds = tileset.tile_uv_bounds(tile_id);
+
+    let (flip_u, flip_v) = if tile.flip_diagonal {
+        (tile.flip_y, tile.flip_x)
+    } else {
+        (tile.flip_x, tile.flip_y)
+    };
+
+    let (left, right) = if flip_u {
+        (uv_bounds.x + uv_bounds.w, uv_bounds.x)
+    } else {
+        (uv_bounds.x, uv_bounds.x + uv_bounds.w)
+    };
+
+    let (top, bottom) = if flip_v {
+        (uv_bounds.y + uv_bounds.h, uv_bounds.y)
+    } else {
+        (uv_bounds.y, uv_bounds.y + uv_bounds.h)
+    };
+
+    Vec4::new(top, left, bottom, right)
+}
@@ -0,0 +1,185 @@
+use crate::graphics::tilemap::TiledProperties;
+use crate::graphics::Bounds;
+use glam::{UVec2, Vec2};
+
+const FLIPPED_HORIZONTALLY_FLAG: u32 = 0x8000_0000;
+const FLIPPED_VERTICALLY_FLAG: u32 = 0x4000_0000;
+const FLIPPED_DIAGONALLY_FLAG: u32 = 0x2000_0000;
+const ROTATED_HEXAGONAL_FLAG: u32 = 0x1000_0000;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Tile {
+    pub gid: u32,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    pub flip_diagonal: bool,
+}
+
+impl Tile {
+    pub const fn new(gid: u32) -> Self {
+        Self {
+            gid,
+            flip_x: false,
+            flip_y: false,
+            flip_diagonal: false,
+        }
+    }
+
+    pub fn from_raw_gid(raw_gid: u32) -> Option<Self> {
+        let gid = raw_gid
+            & !(FLIPPED_HORIZONTALLY_FLAG
+                | FLIPPED_VERTICALLY_FLAG
+                | FLIPPED_DIAGONALLY_FLAG
+                | ROTATED_HEXAGONAL_FLAG);
+
+        (gid != 0).then_some(Self {
+            gid,
+            flip_x: raw_gid & FLIPPED_HORIZONTALLY_FLAG != 0,
+            flip_y: raw_gid & FLIPPED_VERTICALLY_FLAG != 0,
+            flip_diagonal: raw_gid & FLIPPED_DIAGONALLY_FLAG != 0,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TileLayer {
+    pub name: String,
+    pub visible: bool,
+    pub opacity: f32,
+    pub offset: Vec2,
+    pub size: UVec2,
+    pub tiles: Vec<Option<Tile>>,
+    pub properties: TiledProperties,
+}
+
+impl TileLayer {
+    pub fn tile(&self, x: u32, y: u32) -> Option<Tile> {
+        if x >= self.size.x || y >= self.size.y {
+            return None;
+        }
+
+        self.tiles[(y * self.size.x + x) as usize]
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ObjectShape {
+    Rectangle,
+    Ellipse,
+    Point,
+    Polygon(Vec<Vec2>),
+    Polyline(Vec<Vec2>),
+}
+
+#[derive(Clone, Debug)]
+pub struct TiledObject {
+    pub id: u32,
+    pub name: String,
+    pub class: String,
+    pub position: Vec2,
+    pub size: Vec2,
+    pub rotation: f32,
+    pub visible: bool,
+    pub tile: Option<Tile>,
+    pub shape: ObjectShape,
+    pub properties: TiledProperties,
+}
+
+impl TiledObject {
+    pub fn bounds(&self) -> Bounds {
+        match &self.shape {
+            ObjectShape::Polygon(points) | ObjectShape::Polyline(points) => {
+                let min = points
+                    .iter()
+                    .copied()
+                    .fold(Vec2::splat(f32::INFINITY), Vec2::min);
+
+                let max = points
+                    .iter()
+                    .copied()
+                    .fold(Vec2::splat(f32::NEG_INFINITY), Vec2::max);
+
+                if points.is_empty() {
+                    Bounds::new(self.position.x, self.position.y, 0.0, 0.0)
+                } else {
+                    Bounds::from_min_max(self.position + min, self.position + max)
+                }
+            }
+            _ if self.tile.is_some() => {
+                Bounds::new(
+                    self.position.x,
+                    self.position.y - self.size.y,
+                    self.size.x,
+                    self.size.y,
+                )
+            }
+            _ => Bounds::new(self.position.x, self.position.y, self.size.x, self.size.y),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ObjectLayer {
+    pub name: String,
+    pub visible: bool,
+    pub opacity: f32,
+    pub offset: Vec2,
+    pub objects: Vec<TiledObject>,
+    pub properties: TiledProperties,
+}
+
+impl ObjectLayer {
+    pub fn object(&self, name: &str) -> Option<&TiledObject> {
+        self.objects.iter().find(|object| object.name == name)
+    }
+
+    pub fn objects_with_class<'a>(
+        &'a self,
+        class: &'a str,
+    ) -> impl Iterator<Item = &'a TiledObject> + 'a {
+        self.objects.iter().filter(move |object| object.class == class)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum TilemapLayer {
+    Tiles(TileLayer),
+    Objects(ObjectLayer),
+}
+
+impl TilemapLayer {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Tiles(layer) => &layer.name,
+            Self::Objects(layer) => &layer.name,
+        }
+    }
+
+    pub fn visible(&self) -> bool {
+        match self {
+            Self::Tiles(layer) => layer.visible,
+            Self::Objects(layer) => layer.visible,
+        }
+    }
+
+    pub fn properties(&self) -> &TiledProperties {
+        match self {
+            Self::Tiles(layer) => &layer.properties,
+            Self::Objects(layer) => &layer.properties,
+        }
+    }
+
+    pub fn as_tile_layer(&self) -> Option<&TileLayer> {
+        match self {
+            Self::Tiles(layer) => Some(layer),
+            Self::Objects(_) => None,
+        }
+    }
+
+    pub fn as_object_layer(&self) -> Option<&ObjectLayer> {
+        match self {
+            Self::Tiles(_) => None,
+            Self::Objects(layer) => Some(layer),
+        }
+    }
+}
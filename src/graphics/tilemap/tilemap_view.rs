@@ -0,0 +1,154 @@
+use crate::graphics::tilemap::{Tilemap, TilemapLayer};
+use crate::graphics::{
+    impl_drawable_methods, Bounds, Canvas, Color, Drawable, SamplerOptions, Transform,
+};
+use glam::{Affine2, Mat4, Vec2, Vec3};
+
+#[derive(Debug)]
+pub struct TilemapView<'a> {
+    pub tilemap: &'a mut Tilemap,
+    pub layer: Option<usize>,
+    pub time: f32,
+    pub visible_bounds: Option<Bounds>,
+    pub sampler: SamplerOptions,
+    pub transform: Transform,
+    pub anchor_offset: Vec2,
+    pub color: Color,
+}
+
+impl_drawable_methods!(TilemapView<'_>);
+
+impl<'a> TilemapView<'a> {
+    pub fn new(tilemap: &'a mut Tilemap) -> Self {
+        Self {
+            tilemap,
+            layer: None,
+            time: 0.0,
+            visible_bounds: None,
+            sampler: SamplerOptions::NEAREST,
+            transform: Transform::IDENTITY,
+            anchor_offset: Vec2::ZERO,
+            color: Color::WHITE,
+        }
+    }
+
+    pub fn layer(mut self, layer: usize) -> Self {
+        self.layer = Some(layer);
+        self
+    }
+
+    pub fn time(mut self, time: f32) -> Self {
+        self.time = time;
+        self
+    }
+
+    pub fn visible_bounds<B>(mut self, visible_bounds: B) -> Self
+    where
+        B: Into<Bounds>,
+    {
+        self.visible_bounds = Some(visible_bounds.into());
+        self
+    }
+
+    pub fn smooth(mut self, smooth: bool) -> Self {
+        self.sampler = if smooth {
+            SamplerOptions::LINEAR
+        } else {
+            SamplerOptions::NEAREST
+        };
+
+        self
+    }
+
+    pub fn sampler(mut self, sampler: SamplerOptions) -> Self {
+        self.sampler = sampler;
+        self
+    }
+
+    fn draw_layer(&mut self, canvas: &mut Canvas, layer: usize, visible: Bounds) {
+        let Some(TilemapLayer::Tiles(tile_layer)) = self.tilemap.layer(layer) else {
+            return;
+        };
+
+        let mut color = self.color;
+        color.a *= tile_layer.opacity;
+        let linear_color = color.to_linear_vec4();
+
+        let (tilesets, chunks) = self.tilemap.layer_chunks_mut(layer);
+
+        let chunks = chunks
+            .iter_mut()
+            .filter(|chunk| chunk.bounds.intersects(&visible));
+
+        for chunk in chunks {
+            chunk.update(tilesets, self.time, linear_color);
+
+            for batch in chunk.batches.iter_mut() {
+                batch.set_sampler(self.sampler);
+                canvas.draw_static_sprites(batch);
+            }
+        }
+    }
+}
+
+impl Drawable for TilemapView<'_> {
+    fn draw(mut self, canvas: &mut Canvas) {
+        let affine2 =
+            self.transform.to_affine2() * Affine2::from_translation(-self.anchor_offset);
+
+        let visible = self
+            .visible_bounds
+            .unwrap_or_else(|| projection_bounds(canvas.projection()));
+
+        let local_visible = transform_bounds(affine2.inverse(), visible);
+
+        let old_projection = canvas.projection();
+        let anchor_offset = Mat4::from_translation(-self.anchor_offset.extend(0.0));
+        canvas.set_projection(old_projection * self.transform.to_mat4() * anchor_offset);
+
+        match self.layer {
+            Some(layer) => self.draw_layer(canvas, layer, local_visible),
+            None => {
+                for layer in 0..self.tilemap.layers().len() {
+                    if self.tilemap.layers()[layer].visible() {
+                        self.draw_layer(canvas, layer, local_visible);
+                    }
+                }
+            }
+        }
+
+        canvas.set_projection(old_projection);
+    }
+}
+
+fn projection_bounds(projection: Mat4) -> Bounds {
+    let inverse = projection.inverse();
+
+    let corners = [
+        Vec3::new(-1.0, -1.0, 0.0),
+        Vec3::new(1.0, -1.0, 0.0),
+        Vec3::new(-1.0, 1.0, 0.0),
+        Vec3::new(1.0, 1.0, 0.0),
+    ]
+    .map(|corner| inverse.project_point3(corner).truncate());
+
+    points_bounds(&corners)
+}
+
+fn transform_bounds(affine2: Affine2, bounds: Bounds) -> Bounds {
+    let corners = [
+        bounds.top_left(),
+        Vec2::new(bounds.x + bounds.w, bounds.y),
+        Vec2::new(bounds.x, bounds.y + bounds.h),
+        bounds.bottom_right(),
+    ]
+    .map(|corner| affine2.transform_point2(corner));
+
+    points_bounds(&corners)
+}
+
+fn points_bounds(points: &[Vec2]) -> Bounds {
+    let min = points.iter().copied().fold(Vec2::splat(f32::INFINITY), Vec2::min);
+    let max = points.iter().copied().fold(Vec2::splat(f32::NEG_INFINITY), Vec2::max);
+    Bounds::from_min_max(min, max)
+}
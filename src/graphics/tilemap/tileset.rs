@@ -0,0 +1,74 @@
+use crate::graphics::sprite::Texture;
+use crate::graphics::tilemap::TiledProperties;
+use crate::graphics::Bounds;
+use glam::{UVec2, Vec2};
+use rustc_hash::FxHashMap;
+
+#[derive(Clone, Copy, Debug)]
+pub struct TileAnimationFrame {
+    pub tile_id: u32,
+    pub duration: f32,
+}
+
+#[derive(Clone, Debug)]
+pub struct Tileset {
+    pub first_gid: u32,
+    pub name: String,
+    pub tile_size: UVec2,
+    pub columns: u32,
+    pub tile_count: u32,
+    pub margin: u32,
+    pub spacing: u32,
+    pub offset: Vec2,
+    pub texture: Texture,
+    pub animations: FxHashMap<u32, Vec<TileAnimationFrame>>,
+    pub tile_properties: FxHashMap<u32, TiledProperties>,
+    pub properties: TiledProperties,
+}
+
+impl Tileset {
+    pub fn contains_gid(&self, gid: u32) -> bool {
+        gid >= self.first_gid && gid < self.first_gid + self.tile_count
+    }
+
+    pub fn tile_uv_bounds(&self, tile_id: u32) -> Bounds {
+        let columns = self.columns.max(1);
+        let stride = self.tile_size + self.spacing;
+        let position = UVec2::new(tile_id % columns, tile_id / columns) * stride + self.margin;
+
+        Bounds::new(
+            position.x as f32,
+            position.y as f32,
+            self.tile_size.x as f32,
+            self.tile_size.y as f32,
+        )
+    }
+
+    pub fn animated_tile_id(&self, tile_id: u32, time: f32) -> u32 {
+        let Some(frames) = self.animations.get(&tile_id) else {
+            return tile_id;
+        };
+
+        let duration = frames.iter().map(|frame| frame.duration).sum::<f32>();
+
+        if duration <= 0.0 {
+            return tile_id;
+        }
+
+        let mut time = time.rem_euclid(duration);
+
+        for frame in frames {
+            if time < frame.duration {
+                return frame.tile_id;
+            }
+
+            time -= frame.duration;
+        }
+
+        frames.last().map_or(tile_id, |frame| frame.tile_id)
+    }
+
+    pub fn tile_properties(&self, tile_id: u32) -> Option<&TiledProperties> {
+        self.tile_properties.get(&tile_id)
+    }
+}
@@ -0,0 +1,395 @@
+use crate::game::GameResult;
+use crate::graphics::tilemap::{
+    complete_tileset_grid, decode_tile_data, gids_to_tiles, load_tileset_texture, ObjectLayer,
+    ObjectShape, Tile, TileAnimationFrame, TileLayer, TiledObject, TiledProperties, TiledProperty,
+    Tilemap, TilemapLayer, Tileset,
+};
+use crate::graphics::GraphicsContext;
+use anyhow::{anyhow, bail, Context};
+use glam::{UVec2, Vec2};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+#[derive(Deserialize)]
+struct JsonMap {
+    width: u32,
+    height: u32,
+    tilewidth: u32,
+    tileheight: u32,
+    #[serde(default)]
+    orientation: String,
+    #[serde(default)]
+    infinite: bool,
+    #[serde(default)]
+    layers: Vec<JsonLayer>,
+    #[serde(default)]
+    tilesets: Vec<serde_json::Value>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize)]
+struct JsonProperty {
+    name: String,
+    #[serde(default, rename = "type")]
+    property_type: String,
+    value: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct JsonTileset {
+    #[serde(default)]
+    name: String,
+    tilewidth: u32,
+    tileheight: u32,
+    #[serde(default)]
+    columns: u32,
+    #[serde(default)]
+    tilecount: u32,
+    #[serde(default)]
+    margin: u32,
+    #[serde(default)]
+    spacing: u32,
+    #[serde(default)]
+    image: Option<String>,
+    #[serde(default)]
+    tileoffset: Option<JsonPoint>,
+    #[serde(default)]
+    tiles: Vec<JsonTile>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize)]
+struct JsonTile {
+    id: u32,
+    #[serde(default)]
+    animation: Vec<JsonAnimationFrame>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize)]
+struct JsonAnimationFrame {
+    tileid: u32,
+    duration: u32,
+}
+
+#[derive(Deserialize)]
+struct JsonLayer {
+    #[serde(rename = "type")]
+    layer_type: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default = "default_visible")]
+    visible: bool,
+    #[serde(default = "default_opacity")]
+    opacity: f32,
+    #[serde(default)]
+    offsetx: f32,
+    #[serde(default)]
+    offsety: f32,
+    #[serde(default)]
+    width: u32,
+    #[serde(default)]
+    height: u32,
+    #[serde(default)]
+    data: Option<serde_json::Value>,
+    #[serde(default)]
+    encoding: Option<String>,
+    #[serde(default)]
+    compression: Option<String>,
+    #[serde(default)]
+    objects: Vec<JsonObject>,
+    #[serde(default)]
+    layers: Vec<JsonLayer>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize)]
+struct JsonObject {
+    #[serde(default)]
+    id: u32,
+    #[serde(default)]
+    name: String,
+    #[serde(default, rename = "type")]
+    object_type: String,
+    #[serde(default)]
+    class: String,
+    #[serde(default)]
+    x: f32,
+    #[serde(default)]
+    y: f32,
+    #[serde(default)]
+    width: f32,
+    #[serde(default)]
+    height: f32,
+    #[serde(default)]
+    rotation: f32,
+    #[serde(default = "default_visible")]
+    visible: bool,
+    #[serde(default)]
+    gid: Option<u32>,
+    #[serde(default)]
+    ellipse: bool,
+    #[serde(default)]
+    point: bool,
+    #[serde(default)]
+    polygon: Option<Vec<JsonPoint>>,
+    #[serde(default)]
+    polyline: Option<Vec<JsonPoint>>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Clone, Copy, Deserialize)]
+struct JsonPoint {
+    x: f32,
+    y: f32,
+}
+
+impl From<JsonPoint> for Vec2 {
+    fn from(point: JsonPoint) -> Self {
+        Vec2::new(point.x, point.y)
+    }
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+fn default_opacity() -> f32 {
+    1.0
+}
+
+pub(crate) fn load_tmj(graphics: &GraphicsContext, path: &Path) -> GameResult<Tilemap> {
+    let map = read_json::<JsonMap>(path)?;
+    let directory = path.parent().unwrap_or(Path::new(""));
+
+    if !map.orientation.is_empty() && map.orientation != "orthogonal" {
+        bail!("Unsupported tilemap orientation: '{}'", map.orientation);
+    }
+
+    if map.infinite {
+        bail!("Infinite tilemaps are not supported");
+    }
+
+    let tilesets = map
+        .tilesets
+        .into_iter()
+        .map(|tileset| load_tileset(graphics, directory, tileset))
+        .collect::<GameResult<Vec<_>>>()?;
+
+    let mut layers = Vec::new();
+    convert_layers(map.layers, Vec2::ZERO, true, 1.0, &mut layers)?;
+
+    Ok(Tilemap::new(
+        UVec2::new(map.width, map.height),
+        UVec2::new(map.tilewidth, map.tileheight),
+        tilesets,
+        layers,
+        convert_properties(map.properties),
+    ))
+}
+
+fn read_json<T>(path: &Path) -> GameResult<T>
+where
+    T: DeserializeOwned,
+{
+    let json = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read tilemap file: '{}'", path.display()))?;
+
+    serde_json::from_str(&json).map_err(|error| {
+        anyhow!(
+            "Failed to parse tilemap file: '{}': {error}",
+            path.display(),
+        )
+    })
+}
+
+fn load_tileset(
+    graphics: &GraphicsContext,
+    directory: &Path,
+    value: serde_json::Value,
+) -> GameResult<Tileset> {
+    let first_gid = value
+        .get("firstgid")
+        .and_then(serde_json::Value::as_u64)
+        .ok_or_else(|| anyhow!("Tileset is missing 'firstgid'"))? as u32;
+
+    let (json_tileset, directory) = match value.get("source").and_then(|source| source.as_str()) {
+        Some(source) => {
+            let path = directory.join(source);
+
+            if path.extension().is_some_and(|extension| extension == "tsx") {
+                bail!("External TSX tilesets require a TMX map: '{}'", path.display());
+            }
+
+            let json_tileset = read_json::<JsonTileset>(&path)?;
+            (json_tileset, path.parent().unwrap_or(Path::new("")).to_path_buf())
+        }
+        None => {
+            let json_tileset = serde_json::from_value::<JsonTileset>(value)
+                .map_err(|error| anyhow!("Failed to parse tileset: {error}"))?;
+
+            (json_tileset, directory.to_path_buf())
+        }
+    };
+
+    let texture = load_tileset_texture(graphics, &directory, json_tileset.image.as_deref())?;
+
+    let mut tileset = Tileset {
+        first_gid,
+        name: json_tileset.name,
+        tile_size: UVec2::new(json_tileset.tilewidth, json_tileset.tileheight),
+        columns: json_tileset.columns,
+        tile_count: json_tileset.tilecount,
+        margin: json_tileset.margin,
+        spacing: json_tileset.spacing,
+        offset: json_tileset.tileoffset.map_or(Vec2::ZERO, Vec2::from),
+        texture,
+        animations: Default::default(),
+        tile_properties: Default::default(),
+        properties: convert_properties(json_tileset.properties),
+    };
+
+    for tile in json_tileset.tiles {
+        if !tile.animation.is_empty() {
+            let frames = tile
+                .animation
+                .iter()
+                .map(|frame| {
+                    TileAnimationFrame {
+                        tile_id: frame.tileid,
+                        duration: frame.duration as f32 / 1000.0,
+                    }
+                })
+                .collect();
+
+            tileset.animations.insert(tile.id, frames);
+        }
+
+        if !tile.properties.is_empty() {
+            tileset
+                .tile_properties
+                .insert(tile.id, convert_properties(tile.properties));
+        }
+    }
+
+    complete_tileset_grid(&mut tileset)?;
+    Ok(tileset)
+}
+
+fn convert_layers(
+    json_layers: Vec<JsonLayer>,
+    offset: Vec2,
+    visible: bool,
+    opacity: f32,
+    layers: &mut Vec<TilemapLayer>,
+) -> GameResult<()> {
+    for json_layer in json_layers {
+        let offset = offset + Vec2::new(json_layer.offsetx, json_layer.offsety);
+        let visible = visible && json_layer.visible;
+        let opacity = opacity * json_layer.opacity;
+
+        match json_layer.layer_type.as_str() {
+            "tilelayer" => {
+                let size = UVec2::new(json_layer.width, json_layer.height);
+
+                let gids = match json_layer.data {
+                    Some(serde_json::Value::String(data)) => {
+                        decode_tile_data(
+                            &data,
+                            json_layer.encoding.as_deref(),
+                            json_layer.compression.as_deref(),
+                        )?
+                    }
+                    Some(data) => {
+                        serde_json::from_value::<Vec<u32>>(data)
+                            .map_err(|error| anyhow!("Invalid tile data: {error}"))?
+                    }
+                    None => bail!("Tile layer '{}' has no data", json_layer.name),
+                };
+
+                layers.push(TilemapLayer::Tiles(TileLayer {
+                    name: json_layer.name,
+                    visible,
+                    opacity,
+                    offset,
+                    size,
+                    tiles: gids_to_tiles(&gids, size)?,
+                    properties: convert_properties(json_layer.properties),
+                }));
+            }
+            "objectgroup" => {
+                layers.push(TilemapLayer::Objects(ObjectLayer {
+                    name: json_layer.name,
+                    visible,
+                    opacity,
+                    offset,
+                    objects: json_layer
+                        .objects
+                        .into_iter()
+                        .map(|object| convert_object(object, offset))
+                        .collect(),
+                    properties: convert_properties(json_layer.properties),
+                }));
+            }
+            "group" => convert_layers(json_layer.layers, offset, visible, opacity, layers)?,
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+fn convert_object(object: JsonObject, offset: Vec2) -> TiledObject {
+    let shape = if let Some(points) = object.polygon {
+        ObjectShape::Polygon(points.into_iter().map(Vec2::from).collect())
+    } else if let Some(points) = object.polyline {
+        ObjectShape::Polyline(points.into_iter().map(Vec2::from).collect())
+    } else if object.ellipse {
+        ObjectShape::Ellipse
+    } else if object.point {
+        ObjectShape::Point
+    } else {
+        ObjectShape::Rectangle
+    };
+
+    let class = if object.class.is_empty() {
+        object.object_type
+    } else {
+        object.class
+    };
+
+    TiledObject {
+        id: object.id,
+        name: object.name,
+        class,
+        position: Vec2::new(object.x, object.y) + offset,
+        size: Vec2::new(object.width, object.height),
+        rotation: object.rotation.to_radians(),
+        visible: object.visible,
+        tile: object.gid.and_then(Tile::from_raw_gid),
+        shape,
+        properties: convert_properties(object.properties),
+    }
+}
+
+fn convert_properties(json_properties: Vec<JsonProperty>) -> TiledProperties {
+    json_properties
+        .into_iter()
+        .map(|property| {
+            let value = match property.value {
+                serde_json::Value::String(value) => value,
+                value => value.to_string(),
+            };
+
+            let property_value = TiledProperty::parse(&property.property_type, &value);
+            (property.name, property_value)
+        })
+        .collect()
+}
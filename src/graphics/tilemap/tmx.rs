@@ -0,0 +1,312 @@
+use crate::game::GameResult;
+use crate::graphics::tilemap::{
+    complete_tileset_grid, decode_tile_data, gids_to_tiles, load_tileset_texture, ObjectLayer,
+    ObjectShape, Tile, TileAnimationFrame, TileLayer, TiledObject, TiledProperties, TiledProperty,
+    Tilemap, TilemapLayer, Tileset,
+};
+use crate::graphics::GraphicsContext;
+use anyhow::{anyhow, bail, ensure, Context};
+use glam::{UVec2, Vec2};
+use roxmltree::{Document, Node};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+pub(crate) fn load_tmx(graphics: &GraphicsContext, path: &Path) -> GameResult<Tilemap> {
+    let xml = read_xml(path)?;
+    let document = parse_xml(&xml, path)?;
+    let map = document.root_element();
+    let directory = path.parent().unwrap_or(Path::new(""));
+
+    ensure!(
+        map.has_tag_name("map"),
+        "Invalid TMX file: '{}'",
+        path.display(),
+    );
+
+    let orientation = map.attribute("orientation").unwrap_or("orthogonal");
+
+    if orientation != "orthogonal" {
+        bail!("Unsupported tilemap orientation: '{orientation}'");
+    }
+
+    if attribute_or(map, "infinite", 0_u32) != 0 {
+        bail!("Infinite tilemaps are not supported");
+    }
+
+    let tilesets = map
+        .children()
+        .filter(|node| node.has_tag_name("tileset"))
+        .map(|node| load_tileset(graphics, directory, node))
+        .collect::<GameResult<Vec<_>>>()?;
+
+    let mut layers = Vec::new();
+    convert_layers(map, Vec2::ZERO, true, 1.0, &mut layers)?;
+
+    Ok(Tilemap::new(
+        UVec2::new(attribute_or(map, "width", 0), attribute_or(map, "height", 0)),
+        UVec2::new(
+            attribute_or(map, "tilewidth", 0),
+            attribute_or(map, "tileheight", 0),
+        ),
+        tilesets,
+        layers,
+        convert_properties(map),
+    ))
+}
+
+fn read_xml(path: &Path) -> GameResult<String> {
+    fs::read_to_string(path)
+        .with_context(|| format!("Failed to read tilemap file: '{}'", path.display()))
+}
+
+fn parse_xml<'a>(xml: &'a str, path: &Path) -> GameResult<Document<'a>> {
+    Document::parse(xml).map_err(|error| {
+        anyhow!(
+            "Failed to parse tilemap file: '{}': {error}",
+            path.display(),
+        )
+    })
+}
+
+fn attribute<T>(node: Node, name: &str) -> Option<T>
+where
+    T: FromStr,
+{
+    node.attribute(name)?.parse().ok()
+}
+
+fn attribute_or<T>(node: Node, name: &str, default: T) -> T
+where
+    T: FromStr,
+{
+    attribute(node, name).unwrap_or(default)
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| child.has_tag_name(name))
+}
+
+fn load_tileset(graphics: &GraphicsContext, directory: &Path, node: Node) -> GameResult<Tileset> {
+    let first_gid = attribute::<u32>(node, "firstgid")
+        .ok_or_else(|| anyhow!("Tileset is missing 'firstgid'"))?;
+
+    match node.attribute("source") {
+        Some(source) => {
+            let path = directory.join(source);
+            let xml = read_xml(&path)?;
+            let document = parse_xml(&xml, &path)?;
+            let directory = path.parent().unwrap_or(Path::new(""));
+            convert_tileset(graphics, directory, document.root_element(), first_gid)
+        }
+        None => convert_tileset(graphics, directory, node, first_gid),
+    }
+}
+
+fn convert_tileset(
+    graphics: &GraphicsContext,
+    directory: &Path,
+    node: Node,
+    first_gid: u32,
+) -> GameResult<Tileset> {
+    let image = child(node, "image").and_then(|image| image.attribute("source"));
+    let texture = load_tileset_texture(graphics, directory, image)?;
+
+    let offset = child(node, "tileoffset").map_or(Vec2::ZERO, |offset| {
+        Vec2::new(attribute_or(offset, "x", 0.0), attribute_or(offset, "y", 0.0))
+    });
+
+    let mut tileset = Tileset {
+        first_gid,
+        name: node.attribute("name").unwrap_or_default().to_string(),
+        tile_size: UVec2::new(
+            attribute_or(node, "tilewidth", 0),
+            attribute_or(node, "tileheight", 0),
+        ),
+        columns: attribute_or(node, "columns", 0),
+        tile_count: attribute_or(node, "tilecount", 0),
+        margin: attribute_or(node, "margin", 0),
+        spacing: attribute_or(node, "spacing", 0),
+        offset,
+        texture,
+        animations: Default::default(),
+        tile_properties: Default::default(),
+        properties: convert_properties(node),
+    };
+
+    for tile in node.children().filter(|node| node.has_tag_name("tile")) {
+        let id = attribute_or(tile, "id", 0_u32);
+
+        if let Some(animation) = child(tile, "animation") {
+            let frames = animation
+                .children()
+                .filter(|node| node.has_tag_name("frame"))
+                .map(|frame| {
+                    TileAnimationFrame {
+                        tile_id: attribute_or(frame, "tileid", 0),
+                        duration: attribute_or(frame, "duration", 0_u32) as f32 / 1000.0,
+                    }
+                })
+                .collect();
+
+            tileset.animations.insert(id, frames);
+        }
+
+        let properties = convert_properties(tile);
+
+        if !properties.is_empty() {
+            tileset.tile_properties.insert(id, properties);
+        }
+    }
+
+    complete_tileset_grid(&mut tileset)?;
+    Ok(tileset)
+}
+
+fn convert_layers(
+    parent: Node,
+    offset: Vec2,
+    visible: bool,
+    opacity: f32,
+    layers: &mut Vec<TilemapLayer>,
+) -> GameResult<()> {
+    for node in parent.children().filter(Node::is_element) {
+        let name = node.attribute("name").unwrap_or_default().to_string();
+        let offset = offset
+            + Vec2::new(
+                attribute_or(node, "offsetx", 0.0),
+                attribute_or(node, "offsety", 0.0),
+            );
+        let visible = visible && attribute_or(node, "visible", 1_u32) != 0;
+        let opacity = opacity * attribute_or(node, "opacity", 1.0);
+
+        match node.tag_name().name() {
+            "layer" => {
+                let size = UVec2::new(
+                    attribute_or(node, "width", 0),
+                    attribute_or(node, "height", 0),
+                );
+
+                let data = child(node, "data")
+                    .ok_or_else(|| anyhow!("Tile layer '{name}' has no data"))?;
+
+                if child(data, "chunk").is_some() {
+                    bail!("Infinite tilemaps are not supported");
+                }
+
+                let encoding = data.attribute("encoding");
+
+                let gids = if encoding.is_none() && child(data, "tile").is_some() {
+                    data.children()
+                        .filter(|node| node.has_tag_name("tile"))
+                        .map(|tile| attribute_or(tile, "gid", 0))
+                        .collect()
+                } else {
+                    decode_tile_data(
+                        data.text().unwrap_or_default(),
+                        encoding,
+                        data.attribute("compression"),
+                    )?
+                };
+
+                layers.push(TilemapLayer::Tiles(TileLayer {
+                    name,
+                    visible,
+                    opacity,
+                    offset,
+                    size,
+                    tiles: gids_to_tiles(&gids, size)?,
+                    properties: convert_properties(node),
+                }));
+            }
+            "objectgroup" => {
+                layers.push(TilemapLayer::Objects(ObjectLayer {
+                    name,
+                    visible,
+                    opacity,
+                    offset,
+                    objects: node
+                        .children()
+                        .filter(|node| node.has_tag_name("object"))
+                        .map(|object| convert_object(object, offset))
+                        .collect(),
+                    properties: convert_properties(node),
+                }));
+            }
+            "group" => convert_layers(node, offset, visible, opacity, layers)?,
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+fn convert_object(node: Node, offset: Vec2) -> TiledObject {
+    let shape = if let Some(polygon) = child(node, "polygon") {
+        ObjectShape::Polygon(parse_points(polygon))
+    } else if let Some(polyline) = child(node, "polyline") {
+        ObjectShape::Polyline(parse_points(polyline))
+    } else if child(node, "ellipse").is_some() {
+        ObjectShape::Ellipse
+    } else if child(node, "point").is_some() {
+        ObjectShape::Point
+    } else {
+        ObjectShape::Rectangle
+    };
+
+    let class = node
+        .attribute("class")
+        .or_else(|| node.attribute("type"))
+        .unwrap_or_default()
+        .to_string();
+
+    TiledObject {
+        id: attribute_or(node, "id", 0),
+        name: node.attribute("name").unwrap_or_default().to_string(),
+        class,
+        position: Vec2::new(attribute_or(node, "x", 0.0), attribute_or(node, "y", 0.0)) + offset,
+        size: Vec2::new(
+            attribute_or(node, "width", 0.0),
+            attribute_or(node, "height", 0.0),
+        ),
+        rotation: attribute_or(node, "rotation", 0.0_f32).to_radians(),
+        visible: attribute_or(node, "visible", 1_u32) != 0,
+        tile: attribute(node, "gid").and_then(Tile::from_raw_gid),
+        shape,
+        properties: convert_properties(node),
+    }
+}
+
+fn parse_points(node: Node) -> Vec<Vec2> {
+    node.attribute("points")
+        .unwrap_or_default()
+        .split_whitespace()
+        .filter_map(|point| {
+            let (x, y) = point.split_once(',')?;
+            Some(Vec2::new(x.parse().ok()?, y.parse().ok()?))
+        })
+        .collect()
+}
+
+fn convert_properties(node: Node) -> TiledProperties {
+    let Some(properties) = child(node, "properties") else {
+        return TiledProperties::default();
+    };
+
+    properties
+        .children()
+        .filter(|node| node.has_tag_name("property"))
+        .map(|property| {
+            let name = property.attribute("name").unwrap_or_default().to_string();
+            let property_type = property.attribute("type").unwrap_or("string");
+
+            let value = property
+                .attribute("value")
+                .or_else(|| property.text())
+                .unwrap_or_default();
+
+            (name, TiledProperty::parse(property_type, value))
+        })
+        .collect()
+}
+
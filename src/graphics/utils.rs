@@ -1,3 +1,9 @@
+use crate::game::{GameError, GameResult};
+use crate::graphics::WgpuContext;
+use image::{DynamicImage, ImageError};
+use std::path::Path;
+use wgpu::util::DeviceExt;
+
 macro_rules! vertex_attr_array {
     ($ty:ty { $($location:expr => $field:ident: $field_ty:ident,)* }) => {{
         let vertex: $ty = Default::default();
@@ -13,3 +19,73 @@ macro_rules! vertex_attr_array {
 }
 
 pub(crate) use vertex_attr_array;
+
+pub(crate) fn write_buffer(
+    wgpu: &WgpuContext,
+    buffer: &mut Option<wgpu::Buffer>,
+    label: &str,
+    usage: wgpu::BufferUsages,
+    contents: &[u8],
+) {
+    if contents.is_empty() {
+        return;
+    }
+
+    match buffer.as_ref() {
+        Some(buffer) if contents.len() as wgpu::BufferAddress <= buffer.size() => {
+            wgpu.queue().write_buffer(buffer, 0, contents);
+        }
+        _ => {
+            *buffer = Some(wgpu.device().create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some(label),
+                    contents,
+                    usage: usage | wgpu::BufferUsages::COPY_DST,
+                },
+            ));
+        }
+    }
+}
+
+const ENABLED_IMAGE_FORMATS: &[(&str, bool)] = &[
+    ("png", true),
+    ("bmp", cfg!(feature = "bmp")),
+    ("dds", cfg!(feature = "dds")),
+    ("gif", cfg!(feature = "gif")),
+    ("jpeg", cfg!(feature = "jpeg")),
+    ("qoi", cfg!(feature = "qoi")),
+    ("tga", cfg!(feature = "tga")),
+    ("webp", cfg!(feature = "webp")),
+];
+
+pub(crate) fn open_image(path: &Path) -> GameResult<DynamicImage> {
+    image::open(path).map_err(|error| {
+        convert_image_error(
+            error,
+            format!("Failed to open image file: '{}'", path.display()),
+        )
+    })
+}
+
+pub(crate) fn load_image_from_memory(bytes: &[u8]) -> GameResult<DynamicImage> {
+    image::load_from_memory(bytes)
+        .map_err(|error| convert_image_error(error, "Failed to decode image data".to_string()))
+}
+
+fn convert_image_error(error: ImageError, message: String) -> GameError {
+    match error {
+        ImageError::Unsupported(_) => {
+            let enabled_formats = ENABLED_IMAGE_FORMATS
+                .iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(format, _)| *format)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            GameError::new(error).context(format!(
+                "{message}. Enabled image formats: {enabled_formats}",
+            ))
+        }
+        _ => GameError::new(error).context(message),
+    }
+}
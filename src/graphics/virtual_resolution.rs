@@ -0,0 +1,100 @@
+use crate::graphics::{Bounds, Color};
+use glam::{UVec2, Vec2};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum ScalingMode {
+    #[default]
+    Integer,
+    Fractional,
+    Stretch,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct VirtualResolution {
+    size: UVec2,
+    pub scaling_mode: ScalingMode,
+    pub bar_color: Color,
+    pub smooth: bool,
+}
+
+impl VirtualResolution {
+    pub fn new<S>(size: S) -> Self
+    where
+        S: Into<UVec2>,
+    {
+        Self {
+            size: size.into().max(UVec2::ONE),
+            scaling_mode: ScalingMode::Integer,
+            bar_color: Color::BLACK,
+            smooth: false,
+        }
+    }
+
+    pub fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    pub fn scaling_mode(mut self, scaling_mode: ScalingMode) -> Self {
+        self.scaling_mode = scaling_mode;
+        self
+    }
+
+    pub fn bar_color(mut self, bar_color: Color) -> Self {
+        self.bar_color = bar_color;
+        self
+    }
+
+    pub fn smooth(mut self, smooth: bool) -> Self {
+        self.smooth = smooth;
+        self
+    }
+
+    pub fn output_viewport<S>(&self, surface_size: S) -> Bounds
+    where
+        S: Into<UVec2>,
+    {
+        let surface_size = surface_size.into().as_vec2();
+        let size = self.size.as_vec2();
+
+        let output_size = match self.scaling_mode {
+            ScalingMode::Integer => {
+                let scale = (surface_size / size).min_element();
+
+                if scale >= 1.0 {
+                    size * scale.floor()
+                } else {
+                    size * scale
+                }
+            }
+            ScalingMode::Fractional => size * (surface_size / size).min_element(),
+            ScalingMode::Stretch => surface_size,
+        };
+
+        let output_size = output_size.min(surface_size);
+        let position = ((surface_size - output_size) * 0.5).floor().max(Vec2::ZERO);
+        Bounds::new(position.x, position.y, output_size.x, output_size.y)
+    }
+
+    pub fn logical_coords<C, S>(&self, window_coords: C, surface_size: S) -> Vec2
+    where
+        C: Into<Vec2>,
+        S: Into<UVec2>,
+    {
+        let viewport = self.output_viewport(surface_size);
+
+        if viewport.w <= 0.0 || viewport.h <= 0.0 {
+            return Vec2::ZERO;
+        }
+
+        (window_coords.into() - viewport.position()) / viewport.size() * self.size.as_vec2()
+    }
+
+    pub fn window_coords<C, S>(&self, logical_coords: C, surface_size: S) -> Vec2
+    where
+        C: Into<Vec2>,
+        S: Into<UVec2>,
+    {
+        let viewport = self.output_viewport(surface_size);
+        logical_coords.into() / self.size.as_vec2() * viewport.size() + viewport.position()
+    }
+}
@@ -5,7 +5,7 @@ pub mod game;
 pub mod graphics;
 pub mod time;
 
-pub use {anyhow, glam, wgpu, winit};
+pub use {anyhow, glam, image, wgpu, winit};
 
 use crate::game::{Config, Context, Game, GameBuilder, GameResult, ShouldExit};
 use crate::time::GamePhase;
@@ -53,10 +53,8 @@ where
             }
             Event::WindowEvent { event, .. } => {
                 match event {
-                    WindowEvent::CloseRequested => {
-                        if game.on_exit_request(ctx).should_exit() {
-                            event_loop.exit();
-                        }
+                    WindowEvent::CloseRequested if game.on_exit_request(ctx).should_exit() => {
+                        event_loop.exit();
                     }
                     WindowEvent::Resized(size) => {
                         let size = UVec2::new(size.width, size.height);
@@ -77,13 +75,11 @@ where
                         let position = DVec2::new(position.x, position.y);
                         game.on_cursor_move(ctx, position);
                     }
-                    WindowEvent::RedrawRequested => {
-                        if ctx.graphics.update_surface_texture() {
-                            ctx.time.phase = GamePhase::Draw;
-                            if let Err(error) = game.draw(ctx) {
-                                if game.handle_error(ctx, error).should_exit() {
-                                    event_loop.exit();
-                                }
+                    WindowEvent::RedrawRequested if ctx.graphics.update_surface_texture() => {
+                        ctx.time.phase = GamePhase::Draw;
+                        if let Err(error) = game.draw(ctx) {
+                            if game.handle_error(ctx, error).should_exit() {
+                                event_loop.exit();
                             }
                         }
                     }